@@ -1,13 +1,15 @@
-use clap;
 use clap::Parser;
 use inline_colorization::*;
 use rand::Rng;
-use serde_json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
+use std::fmt;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use unicode_normalization::UnicodeNormalization;
 
 #[derive(clap::Parser, Debug)]
 struct Cli {
@@ -20,30 +22,357 @@ struct Cli {
     #[clap(long, default_value_t = false)]
     // hard mode (any yellow/green letters will need to be used on next guesses and green letters must stay where they are)
     hard: bool,
+    #[clap(long, default_value_t = false, conflicts_with = "hard")]
+    // easy mode (every vowel of the word is revealed at the start of the game)
+    easy: bool,
     #[clap(short, long, default_value_t = false)]
     // turn words.json into corrected words.txt
     format_json: bool,
+    #[clap(long, default_value_t = false)]
+    // compact mode (only the latest guess and the keyboard are shown, type /board while playing to see every guess)
+    compact: bool,
+    #[clap(long, env = "RUDLE_FIXED_WORD")]
+    // always play this word instead of a random one (handy for testing)
+    fixed_word: Option<String>,
+    #[clap(long, default_value_t = false)]
+    // show the answer while playing (debug builds ask before every game)
+    reveal: bool,
+    #[clap(long, default_value_t = false)]
+    // letters match their accented versions (typing "e" matches "é") while the answer keeps its accents
+    normalize_accents: bool,
+    #[clap(short, long)]
+    // only pick answers with this many letters (for dictionaries with words of different lengths)
+    length: Option<usize>,
+    #[clap(long)]
+    // reveal a letter after this many guesses in a row without a green letter
+    mercy: Option<u64>,
+    #[clap(long, default_value_t = false)]
+    // play the daily puzzle, the same word for everyone on the same day
+    daily: bool,
+    #[clap(long, allow_negative_numbers = true)]
+    // play the daily puzzle from this many days ago (negative) or ahead (positive)
+    daily_offset: Option<i64>,
+    #[clap(long, default_value_t = false, conflicts_with_all = ["daily", "daily_offset"])]
+    // play the puzzle of the hour, the word changes every (UTC) hour (for events and streams)
+    hourly: bool,
+    #[clap(long, default_value_t = false)]
+    // accept any guess of the right length, even if it isn't in the dictionary
+    free_play: bool,
+    #[clap(long)]
+    // TOML file with the colors of the tiles (tables correct, missed and incorrect with fg and bg color names)
+    theme: Option<PathBuf>,
+    #[clap(long)]
+    // only pick answers that have all of these letters (e.g. qz to practice words with q and z)
+    contains: Option<String>,
+    #[clap(long, default_value_t = false)]
+    // hardcore mode (a single invalid or wrong length guess loses the game)
+    hardcore: bool,
+    #[clap(long, default_value_t = false)]
+    // show the letters every position can still hold under the board
+    assist: bool,
+    #[clap(long = "dict", value_name = "NAME=PATH")]
+    // register a named dictionary that can be switched to from the options (can be repeated)
+    dicts: Vec<String>,
+    #[clap(long)]
+    // read input from this file one line at a time before switching to what is typed (for reproducing bugs)
+    attempts_file: Option<PathBuf>,
+    #[clap(long)]
+    // only pick answers where no letter shows up more than this many times
+    max_repeats: Option<usize>,
+    #[clap(long, value_enum, default_value_t = ColoringMode::Standard)]
+    // how extra copies of a letter are colored (any-occurrence makes every copy yellow)
+    coloring: ColoringMode,
+    #[clap(long, default_value_t = false)]
+    // show the most common letter at each position among the possible answers when a game starts
+    letter_odds: bool,
+    #[clap(long, default_value_t = false)]
+    // put the try number in front of every guess on the board
+    numbered: bool,
+    #[clap(long, default_value_t = false)]
+    // ring the terminal bell when a guess finds a new green letter (and three times on a win)
+    bell: bool,
+    #[clap(long)]
+    // only pick answers among the N most common words (needs a dictionary with word frequencies)
+    common: Option<usize>,
+    #[clap(long, default_value_t = false)]
+    // show how long the game has been going on in the header
+    show_timer: bool,
+    #[clap(long, default_value_t = false, conflicts_with_all = ["compact", "assist"])]
+    // blind mode (guesses only tell how many letters are correct or present, the board is shown when the game is over)
+    blind: bool,
+    #[clap(long, default_value_t = false)]
+    // once half of the tries are used, every guess that doesn't win reveals a letter (the last one is never given)
+    progressive_hints: bool,
+    #[clap(long)]
+    // write the board of every finished game to this HTML file (a standalone page to share or embed)
+    export_html: Option<PathBuf>,
+    #[clap(long, value_enum)]
+    // draw words you lost on before more often (practice) or less often (confidence)
+    bias: Option<SelectionBias>,
+    #[clap(long, default_value_t = false)]
+    // after a game, mark which guesses were among the most informative ones and list the wasted ones
+    coach: bool,
+    #[clap(long, value_enum, default_value_t = LengthPolicy::Reject)]
+    // what to do with guesses of the wrong length: reject them, truncate long ones or also pad short ones
+    length_policy: LengthPolicy,
+    #[clap(long, default_value_t = false)]
+    // never clear the screen, a separator line is printed instead so the scrollback is kept
+    no_clear: bool,
+    #[clap(long, default_value_t = false)]
+    // show the keyboard of the compact board in a shuffled order (a different one every game)
+    scramble_keyboard: bool,
+    #[clap(long, default_value_t = false)]
+    // team mode (two players alternate, every round takes a guess from each one before the board is shown)
+    team: bool,
+    #[clap(long, default_value_t = false)]
+    // tell how many possible answers the word is drawn from when a game starts
+    show_pool: bool,
+    #[clap(long, value_enum)]
+    // only pick answers at least this hard (words with rarer letters are harder)
+    min_difficulty: Option<Difficulty>,
+    #[clap(long)]
+    // don't show the guess while it is typed, only the scored row (needs a terminal that can turn
+    // echo off, piped input is read as usual)
+    hidden_input: bool,
+    #[clap(long, value_name = "POSITION=LETTER")]
+    // only pick answers with this letter at this position (from 1), e.g. 5=y for words ending in y
+    fixed: Option<String>,
+    #[clap(long)]
+    // show the alphabet under the board with the letters already guessed dimmed
+    alphabet_strip: bool,
+    #[clap(long)]
+    // explain the accuracy at the end of a game (tile counts and points)
+    verbose: bool,
+    #[clap(long, value_name = "SEED", conflicts_with_all = ["daily", "daily_offset", "hourly"])]
+    // play the answers in an order shuffled by this seed, everyone with the same seed gets the
+    // same words game after game
+    tournament: Option<u64>,
+    #[clap(long, value_name = "PHRASE", conflicts_with_all = ["tournament", "daily", "daily_offset", "hourly"])]
+    // like --tournament with a phrase instead of a number, easier to share
+    seed_phrase: Option<String>,
+    #[clap(long, value_enum)]
+    // skip the menus and talk JSON on stdin/stdout instead, for frontends (see the README)
+    protocol: Option<Protocol>,
+    #[clap(long, conflicts_with_all = ["hourly", "tournament", "seed_phrase"])]
+    // play a daily puzzle picked among the medium difficulty words only, so no day gets an obscure
+    // one (works with --daily-offset too)
+    balanced_daily: bool,
+    #[clap(long, value_name = "SECONDS")]
+    // the game is lost once it took this long in total, like a chess clock
+    time_budget: Option<u64>,
+    #[clap(long)]
+    // underline the letters of the latest guess that changed since the guess before
+    show_changes: bool,
+    #[clap(long)]
+    // words that aren't in the dictionary can be guessed even with strict on, but take two tries
+    risky: bool,
+    #[clap(long, value_name = "PATH")]
+    // JSON map of words to their definition, the answer's is shown when a game ends
+    definitions: Option<PathBuf>,
+    #[clap(long)]
+    // after every guess, show how much it told apart the possible answers (in bits)
+    entropy_gauge: bool,
+    #[clap(long)]
+    // allow taking back one guess per game with /mulligan (it still counts in the accuracy)
+    mulligan: bool,
+    #[clap(long)]
+    // show an arcade score on a win that goes down with every second and every try it took
+    arcade: bool,
+    #[clap(long)]
+    // show the letters known to be in the word that no green places yet, once per copy
+    tray: bool,
+    #[clap(long, conflicts_with_all = ["assist", "tray", "entropy_gauge"])]
+    // harder: yellow letters are shown gray, only the greens give anything away
+    fog: bool,
+}
+
+/// Shapes of `words.json` that `--format-json` knows how to read.
+#[derive(serde::Deserialize, Debug)]
+#[serde(untagged)]
+enum WordSource {
+    // {"word": frequency, ...}
+    Frequencies(HashMap<String, u64>),
+    // ["word", ...]
+    List(Vec<String>),
+}
+
+impl WordSource {
+    fn parse(contents: &str) -> Result<Self, Box<dyn Error>> {
+        serde_json::from_str(contents).map_err(|_| {
+            "expected either a map of words to their frequency or an array of words".into()
+        })
+    }
+
+    fn into_words(self) -> Vec<String> {
+        match self {
+            WordSource::Frequencies(words) => words.into_keys().collect(),
+            WordSource::List(words) => words,
+        }
+    }
+}
+
+/// Reads a word list stored as a word array, a frequency map or one word per line.
+fn read_word_list(path: &PathBuf) -> Result<Vec<String>, Box<dyn Error>> {
+    let contents = std::fs::read_to_string(path)?;
+    match WordSource::parse(&contents) {
+        Ok(source) => Ok(source.into_words()),
+        Err(_) => Ok(contents
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()),
+    }
+}
+
+/// Adds `word` to a dictionary file keeping its format (word array, frequency map or one word per line).
+/// Returns `false` if the word was already there.
+fn append_word(path: &PathBuf, word: &str) -> Result<bool, Box<dyn Error>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => "[]".to_string(),
+        Err(e) => return Err(e.into()),
+    };
+    match WordSource::parse(&contents) {
+        Ok(WordSource::List(mut words)) => {
+            if words.iter().any(|w| w == word) {
+                return Ok(false);
+            }
+            words.push(word.to_string());
+            save_json(path, &words)?;
+        }
+        Ok(WordSource::Frequencies(mut words)) => {
+            if words.contains_key(word) {
+                return Ok(false);
+            }
+            words.insert(word.to_string(), 1);
+            save_json(path, &words)?;
+        }
+        Err(_) => {
+            if contents.lines().any(|line| line.trim() == word) {
+                return Ok(false);
+            }
+            let mut file = std::fs::OpenOptions::new().append(true).open(path)?;
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                writeln!(file)?;
+            }
+            writeln!(file, "{}", word)?;
+        }
+    }
+    Ok(true)
 }
 
+// turn words.json into corrected words.txt
+fn format_json() -> Result<(), Box<dyn Error>> {
+    let mut file = File::open("words.json")?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    // frequencies are kept so --common still works with the formatted list
+    match WordSource::parse(&contents)? {
+        WordSource::Frequencies(words) => {
+            let formatted = words
+                .into_iter()
+                .filter(|(word, _)| word.len() == 5)
+                .collect::<std::collections::BTreeMap<String, u64>>();
+            serde_json::to_writer_pretty(File::create("words.txt")?, &formatted)?;
+        }
+        WordSource::List(words) => {
+            let formatted = words
+                .into_iter()
+                .filter(|word| word.len() == 5)
+                .collect::<Vec<String>>();
+            serde_json::to_writer_pretty(File::create("words.txt")?, &formatted)?;
+        }
+    }
+    Ok(())
+}
+
+/// Base letter of an accented letter ("é" becomes "e"), anything else is returned as is.
+fn strip_accents(letter: char) -> char {
+    letter
+        .nfd()
+        .find(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .unwrap_or(letter)
+}
+
+#[derive(Clone)]
 struct Dictionary {
     words: Vec<String>,
+    // how common each word is, empty if the word list didn't come with frequencies
+    frequencies: HashMap<String, u64>,
 }
 
 impl Dictionary {
-    #[allow(dead_code)]
     fn new() -> Self {
-        Self { words: Vec::new() }
+        Self {
+            words: Vec::new(),
+            frequencies: HashMap::new(),
+        }
+    }
+
+    /// The word list bundled into the binary, fails if `words.txt` was replaced by something broken.
+    fn try_default() -> Result<Self, Box<dyn Error>> {
+        Self::from_bundled(include_str!("../words.txt"))
+    }
+
+    /// [`Dictionary::parse`] of the contents of the bundled `words.txt`, naming it in the error.
+    fn from_bundled(contents: &str) -> Result<Self, Box<dyn Error>> {
+        Self::parse(contents).map_err(|e| format!("the bundled words.txt is not a valid word list ({})", e).into())
+    }
+
+    /// Parses a word array or a frequency map, the words of a frequency map are sorted so the daily
+    /// puzzles keep their order. Fails if there are no words at all.
+    fn parse(contents: &str) -> Result<Self, Box<dyn Error>> {
+        let dictionary = match WordSource::parse(contents)? {
+            WordSource::List(words) => Self {
+                words,
+                frequencies: HashMap::new(),
+            },
+            WordSource::Frequencies(frequencies) => {
+                let mut words = frequencies.keys().cloned().collect::<Vec<String>>();
+                words.sort();
+                Self { words, frequencies }
+            }
+        };
+        if dictionary.words.is_empty() {
+            return Err("the word list is empty".into());
+        }
+        Ok(dictionary)
+    }
+
+    /// The words `keep` accepts, along with their frequencies.
+    fn filtered(&self, keep: impl Fn(&String) -> bool) -> Self {
+        let words = self.words.iter().filter(|w| keep(w)).cloned().collect::<Vec<String>>();
+        let frequencies = words
+            .iter()
+            .filter_map(|w| self.frequencies.get(w).map(|f| (w.clone(), *f)))
+            .collect();
+        Self { words, frequencies }
+    }
+
+    /// The `n` most common words, ties broken alphabetically. Fails if there are no frequencies.
+    fn top_common(&self, n: usize) -> Result<Self, Box<dyn Error>> {
+        if self.frequencies.is_empty() {
+            return Err("the dictionary has no word frequencies, load a frequency map with -w".into());
+        }
+        let mut ranked = self.words.iter().collect::<Vec<&String>>();
+        ranked.sort_by(|a, b| {
+            let frequency = |w: &String| self.frequencies.get(w).copied().unwrap_or(0);
+            frequency(b).cmp(&frequency(a)).then(a.cmp(b))
+        });
+        let top = ranked.into_iter().take(n).collect::<HashSet<&String>>();
+        Ok(self.filtered(|w| top.contains(w)))
     }
 
     fn load(&mut self, path: PathBuf, append: bool) -> Result<(), Box<dyn Error>> {
         let mut file = File::open(path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
-        let mut words: Vec<String> = serde_json::from_str(&contents)?;
+        let mut loaded = Self::parse(&contents)?;
         if append {
-            self.words.append(&mut words);
+            self.words.append(&mut loaded.words);
+            self.frequencies.extend(loaded.frequencies);
         } else {
-            self.words = words;
+            *self = loaded;
         }
         Ok(())
     }
@@ -53,9 +382,264 @@ impl Dictionary {
         self.words[rng.gen_range(0..self.words.len())].clone()
     }
 
+    /// The words of `exclude` (oldest first) to skip: the latest ones that are in the dictionary,
+    /// but never all of its words so there is always one left to draw.
+    fn recently_drawn<'a>(&self, exclude: &'a VecDeque<String>) -> HashSet<&'a String> {
+        let limit = self.words.iter().collect::<HashSet<&String>>().len().saturating_sub(1);
+        let mut skipped = HashSet::new();
+        for word in exclude.iter().rev().filter(|w| self.words.contains(w)) {
+            if skipped.len() == limit {
+                break;
+            }
+            skipped.insert(word);
+        }
+        skipped
+    }
+
+    /// Same as [`Dictionary::random`] but skips the latest words of `exclude`, as many as the
+    /// dictionary can spare. Fails if the dictionary is empty.
+    fn random_excluding(&self, exclude: &VecDeque<String>) -> Result<String, Errors> {
+        if self.words.is_empty() {
+            return Err(Errors::NoWordFound);
+        }
+        let skipped = self.recently_drawn(exclude);
+        let candidates = self
+            .words
+            .iter()
+            .filter(|w| !skipped.contains(w))
+            .collect::<Vec<&String>>();
+        let mut rng = rand::thread_rng();
+        Ok(candidates[rng.gen_range(0..candidates.len())].clone())
+    }
+
+    /// Like [`Dictionary::random_excluding`] with every word weighted by how often it was lost in
+    /// `losses`: `Practice` makes lost words up to 4 times more likely, `Confidence` makes them up
+    /// to 4 times less likely. No word ever gets a weight of zero. Fails if the dictionary is empty.
+    fn random_with_bias(
+        &self,
+        losses: &HashMap<String, u64>,
+        bias: SelectionBias,
+        exclude: &VecDeque<String>,
+    ) -> Result<String, Errors> {
+        if self.words.is_empty() {
+            return Err(Errors::NoWordFound);
+        }
+        let skipped = self.recently_drawn(exclude);
+        let candidates = self
+            .words
+            .iter()
+            .filter(|w| !skipped.contains(w))
+            .collect::<Vec<&String>>();
+        let weights = candidates.iter().map(|w| {
+            let lost = losses.get(*w).copied().unwrap_or(0).min(3);
+            match bias {
+                SelectionBias::Practice => 1 + lost,
+                SelectionBias::Confidence => 4 - lost,
+            }
+        });
+        let index = rand::distributions::WeightedIndex::new(weights).map_err(|_| Errors::NoWordFound)?;
+        Ok(candidates[rand::distributions::Distribution::sample(&index, &mut rand::thread_rng())].clone())
+    }
+
+    /// The words in the order the puzzles go through them: sorted and then shuffled the same way
+    /// every time, so the order doesn't depend on how the file was written and the next answer
+    /// can't be guessed from the last one.
+    fn puzzle_order(&self) -> Vec<String> {
+        let mut words = self.words.clone();
+        words.sort();
+        words.dedup();
+        rand::seq::SliceRandom::shuffle(
+            words.as_mut_slice(),
+            &mut <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(PUZZLE_ORDER_SEED),
+        );
+        words
+    }
+
+    /// The answer of daily puzzle number `index`, puzzles follow [`Dictionary::puzzle_order`].
+    fn daily(&self, index: i64) -> Option<String> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.puzzle_order().get(i).cloned())
+    }
+
+    /// Like [`Dictionary::daily`] but only among the medium words of [`Dictionary::difficulty`],
+    /// picked at random with `index` as the seed so a day always gets the same word.
+    fn daily_balanced(&self, index: i64) -> Option<String> {
+        let index = u64::try_from(index).ok()?;
+        let ratings = self.difficulty();
+        let medium = self.filtered(|w| ratings.get(w) == Some(&Difficulty::Medium));
+        if medium.words.is_empty() {
+            return None;
+        }
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(index);
+        Some(medium.words[rng.gen_range(0..medium.words.len())].clone())
+    }
+
     fn have(&self, word: &str) -> bool {
         self.words.contains(&word.to_string())
     }
+
+    /// How many words there are of each length, shortest first.
+    fn lengths(&self) -> Vec<(usize, usize)> {
+        let mut lengths: HashMap<usize, usize> = HashMap::new();
+        for word in &self.words {
+            *lengths.entry(word.chars().count()).or_insert(0) += 1;
+        }
+        let mut lengths = lengths.into_iter().collect::<Vec<(usize, usize)>>();
+        lengths.sort();
+        lengths
+    }
+
+    /// The `n` most common 2-letter starts and ends of the words with their counts, most common
+    /// first (ties alphabetically).
+    fn common_affixes(&self, n: usize) -> (AffixCounts, AffixCounts) {
+        let top = |affix: &dyn Fn(&[char]) -> String| {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for word in &self.words {
+                let letters = word.chars().collect::<Vec<char>>();
+                if letters.len() >= 2 {
+                    *counts.entry(affix(&letters)).or_insert(0) += 1;
+                }
+            }
+            let mut counts = counts.into_iter().collect::<Vec<(String, usize)>>();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            counts.truncate(n);
+            counts
+        };
+        (
+            top(&|letters| letters[..2].iter().collect()),
+            top(&|letters| letters[letters.len() - 2..].iter().collect()),
+        )
+    }
+
+    /// Whether there is at least one word with `n` letters.
+    fn contains_length(&self, n: usize) -> bool {
+        self.words.iter().any(|w| w.chars().count() == n)
+    }
+
+    /// The words shuffled the same way every time for `seed`.
+    fn shuffled(&self, seed: u64) -> Vec<String> {
+        let mut words = self.words.clone();
+        rand::seq::SliceRandom::shuffle(
+            words.as_mut_slice(),
+            &mut <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed),
+        );
+        words
+    }
+
+    /// Words that can't work as answers: not `length` letters long, not only made of letters, or
+    /// rejected as a guess by `blocked` so they could never be guessed.
+    fn validate_answers(&self, length: usize, blocked: &HashSet<String>) -> Vec<String> {
+        self.words
+            .iter()
+            .filter(|w| {
+                w.chars().count() != length
+                    || !w.chars().all(char::is_alphabetic)
+                    || blocked.contains(&w.to_lowercase())
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// A line describing the length histogram if the words aren't all the same length.
+    fn mixed_lengths_warning(&self) -> Option<String> {
+        let lengths = self.lengths();
+        if lengths.len() <= 1 {
+            return None;
+        }
+        Some(format!(
+            "The dictionary has words of different lengths ({}), use --length to only play one of them.",
+            lengths
+                .iter()
+                .map(|(length, count)| format!("{} letters: {}", length, count))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ))
+    }
+
+    fn with_length(&self, length: usize) -> Self {
+        self.filtered(|w| w.chars().count() == length)
+    }
+
+    /// Only the words with `letter` at `position` (counted from 0).
+    fn with_fixed(&self, position: usize, letter: char) -> Self {
+        self.filtered(|w| w.chars().nth(position) == Some(letter))
+    }
+
+    /// Only the words that have every one of `letters`.
+    fn containing(&self, letters: &[char]) -> Self {
+        self.filtered(|w| letters.iter().all(|c| w.contains(*c)))
+    }
+
+    /// How often each letter is at each position among the words with `length` letters, most common
+    /// first (ties alphabetically).
+    fn position_frequencies(&self, length: usize) -> Vec<Vec<(char, usize)>> {
+        let mut counts: Vec<HashMap<char, usize>> = vec![HashMap::new(); length];
+        for word in self.words.iter().filter(|w| w.chars().count() == length) {
+            for (i, letter) in word.chars().enumerate() {
+                *counts[i].entry(letter).or_insert(0) += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .map(|letters| {
+                let mut letters = letters.into_iter().collect::<Vec<(char, usize)>>();
+                letters.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+                letters
+            })
+            .collect()
+    }
+
+    /// Rates every word by how common its letters are among the words of the same length, the most
+    /// common third is easy and the rarest third hard. Repeated letters count as rarer.
+    fn difficulty(&self) -> HashMap<String, Difficulty> {
+        let mut ratings = HashMap::new();
+        for (length, _) in self.lengths() {
+            let words = self.with_length(length).words;
+            let mut shares: HashMap<char, f64> = HashMap::new();
+            for word in &words {
+                for letter in word.chars().collect::<HashSet<char>>() {
+                    *shares.entry(letter).or_insert(0.0) += 1.0 / words.len() as f64;
+                }
+            }
+            let mut scored = words
+                .iter()
+                .map(|word| {
+                    let distinct = word.chars().collect::<HashSet<char>>();
+                    let score = distinct.iter().map(|c| shares[c]).sum::<f64>() / length as f64;
+                    (score, word)
+                })
+                .collect::<Vec<(f64, &String)>>();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(b.1)));
+            for (i, (_, word)) in scored.iter().enumerate() {
+                let rating = match i * 3 / scored.len() {
+                    0 => Difficulty::Easy,
+                    1 => Difficulty::Medium,
+                    _ => Difficulty::Hard,
+                };
+                ratings.insert(word.to_string(), rating);
+            }
+        }
+        ratings
+    }
+
+    /// Only the words rated `min` or harder by [`Dictionary::difficulty`].
+    fn with_min_difficulty(&self, min: Difficulty) -> Self {
+        let ratings = self.difficulty();
+        self.filtered(|w| ratings.get(w).is_some_and(|rating| *rating >= min))
+    }
+
+    /// Only the words where no letter shows up more than `n` times.
+    fn filter_max_repeats(&self, n: usize) -> Self {
+        self.filtered(|w| w.chars().all(|c| w.chars().filter(|l| *l == c).count() <= n))
+    }
+
+    fn have_ignoring_accents(&self, word: &str) -> bool {
+        let word = word.chars().map(strip_accents).collect::<String>();
+        self.words
+            .iter()
+            .any(|w| w.chars().map(strip_accents).collect::<String>() == word)
+    }
 }
 
 struct Game {
@@ -63,391 +647,5189 @@ struct Game {
     word: String,
     guesses: Vec<Vec<Guess>>,
     hard: bool,
+    hardcore: bool,
+    easy: bool,
+    strict: bool,
+    compact: bool,
+    assist: bool,
+    coloring_mode: ColoringMode,
     playing: bool,
     tries: u64,
     max_tries: u64,
-    letter_counts: HashMap<char, i64>,
+    recent_words: VecDeque<String>,
+    max_hints: u64,
+    hints_used: u64,
+    // every letter revealed during the game, by hints or by mercy
+    hints: Vec<(usize, char)>,
+    mercy: Option<u64>,
+    no_progress_streak: u64,
+    mercy_hint: Option<(usize, char)>,
+    fixed_word: Option<String>,
+    reveal: bool,
+    // debug builds ask before every game whether to reveal the answer, unless --reveal was given
+    ask_reveal: bool,
+    letter_odds: bool,
+    numbered: bool,
+    bell: bool,
+    show_timer: bool,
+    // only counts are shown after each guess, the board comes at the end
+    blind: bool,
+    progressive_hints: bool,
+    export_html: Option<PathBuf>,
+    bias: Option<SelectionBias>,
+    normalize_accents: bool,
+    word_length: Option<usize>,
+    required_letters: Vec<char>,
+    max_repeats: Option<usize>,
+    common: Option<usize>,
+    // offset in days from today's daily puzzle, `None` when not playing daily puzzles
+    daily: Option<i64>,
+    daily_number: Option<i64>,
+    // the answer comes from the current hour when not playing daily puzzles
+    hourly: bool,
+    hourly_number: Option<i64>,
+    stats: Stats,
+    started: Option<Instant>,
+    session: Session,
+    config: Config,
+    blocked_guesses: HashSet<String>,
+    theme: Theme,
+    // dictionaries that aren't in use, the active one lives in `dictionary`
+    dictionaries: HashMap<String, Dictionary>,
+    active_dictionary: String,
+    coach: bool,
+    length_policy: LengthPolicy,
+    // keyboard shown in a shuffled order that is drawn from keyboard_seed, a new one every game,
+    // the same ones for everyone playing with the same --tournament or --seed-phrase
+    scramble_keyboard: bool,
+    keyboard_seed: u64,
+    // two players share the tries, each round is one guess from each of them
+    team: bool,
+    // guesses played this game, the turns go on from it in team mode
+    turns: usize,
+    // how many answers the word was drawn from, `None` when it wasn't drawn (fixed or picked words)
+    pool_size: Option<usize>,
+    show_pool: bool,
+    // the letters of the answer were given by /anagram this game
+    anagram_given: bool,
+    min_difficulty: Option<Difficulty>,
+    hidden_input: bool,
+    // (position, letter) every answer has, from --fixed
+    fixed_letter: Option<(usize, char)>,
+    alphabet_strip: bool,
+    verbose: bool,
+    // seed of the --tournament word order
+    tournament: Option<u64>,
+    balanced_daily: bool,
+    // seconds the whole game may take, from --time-budget
+    time_budget: Option<u64>,
+    show_changes: bool,
+    risky: bool,
+    definitions: Option<Definitions>,
+    entropy_gauge: bool,
+    pattern_table: Option<PatternTable>,
+    // --mulligan allows taking back one guess per game
+    mulligan: bool,
+    mulligan_used: bool,
+    // the row the mulligan took back
+    discarded: Option<Vec<Guess>>,
+    arcade: bool,
+    tray: bool,
+    // --fog shows yellows as gray, the rows in `guesses` keep their real colors for the scoring
+    fog: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Guess {
-    Correct(char),
-    Incorrect(char),
-    Missed(char),
+/// Preferences that survive between launches, stored as JSON in the user's config directory.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(default)]
+struct Config {
+    max_hints: u64,
+    // word list of guesses that are refused, nothing is filtered when unset
+    guess_filter: Option<PathBuf>,
+    // letters the words are made of, change it to play in another script
+    alphabet: String,
+    // whether quitting a game in the middle counts as a loss in the stats
+    forfeit_is_loss: bool,
+    // wait for a key press after giving up before showing the answer
+    reveal_pause: bool,
+    // every finished game is appended to this file as a JSON line, for tools adding up results of many players
+    community_log: Option<PathBuf>,
+    // keep the game in progress on disk and offer to resume it on the next launch
+    auto_save: bool,
+    // after a loss, name the guess that was the fewest letter edits away from the answer
+    show_nearest_guess: bool,
+    // milliseconds between showing the answer of a lost game and its accuracy, 0 for no pause
+    loss_reveal_delay_ms: u64,
+    ui: UiConfig,
+    scoring: ScoringConfig,
 }
 
-impl Guess {
-    fn get_letter(&self) -> char {
-        match self {
-            Guess::Correct(letter) => *letter,
-            Guess::Incorrect(letter) => *letter,
-            Guess::Missed(letter) => *letter,
-        }
-    }
+/// Points every tile is worth in the accuracy, which is the average points of a guess out of the
+/// points of an all-correct guess.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(default)]
+struct ScoringConfig {
+    correct: f64,
+    present: f64,
+    absent: f64,
+    // taken off once for every revealed letter
+    hint: f64,
+    // the --arcade score of an instant win in one try, it shrinks by arcade_second_decay
+    // (a fraction) every second and by arcade_try_decay every try after the first
+    arcade_start: f64,
+    arcade_second_decay: f64,
+    arcade_try_decay: f64,
 }
 
-impl Default for Dictionary {
+impl Default for ScoringConfig {
     fn default() -> Self {
-        let a: Vec<String> = serde_json::from_str(include_str!("../words.txt")).unwrap();
-        Self { words: a }
-    }
-}
-
-impl Game {
-    fn new(dictionary: Dictionary, hard: bool) -> Self {
+        // a hint costs as much as the green letter it gives away
         Self {
-            dictionary,
-            word: "".to_string(),
-            guesses: Vec::new(),
-            hard,
-            playing: false,
-            tries: 1,
-            max_tries: 5,
-            letter_counts: HashMap::new(),
+            correct: 2.0,
+            present: 1.0,
+            absent: -0.5,
+            hint: 2.0,
+            arcade_start: 10000.0,
+            arcade_second_decay: 0.005,
+            arcade_try_decay: 0.15,
         }
     }
+}
 
-    fn play(&mut self) -> String {
-        self.playing = true;
-        let word;
-        // this was a test case for the [`Game::determine_guess`] lmao
-        if cfg!(debug_assertions) {
-            word = "teats".to_string();
+impl ScoringConfig {
+    /// The defaults if `correct` isn't positive, since the accuracy is divided by it.
+    fn validated(self) -> (Self, Option<String>) {
+        if self.correct > 0.0 {
+            (self, None)
         } else {
-            word = self.dictionary.random();
+            (
+                Self::default(),
+                Some("scoring.correct in the config must be above 0, using the default scoring".to_string()),
+            )
         }
-        self.word = word.clone();
-        self.letter_counts = word.chars().fold(HashMap::new(), |mut acc, letter| {
-            *acc.entry(letter).or_insert(0) += 1;
-            acc
-        });
-        word
     }
+}
 
-    fn determine_guess(&mut self, input: String) -> Result<Vec<Guess>, Errors> {
-        if input.len() != self.word.len() {
-            return Err(Errors::WordLengthNotEqualsToGuessWord);
-        }
-        if !self.dictionary.have(&input) {
-            return Err(Errors::NoWordFound);
-        }
+/// Banner and prompts shown by the menus and the game.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(default)]
+struct UiConfig {
+    banner: String,
+    selection_prompt: String,
+    option_prompt: String,
+    guess_prompt: String,
+    // art printed when a game ends: "classic", "small" or "off"
+    end_banners: String,
+    // files replacing the built-in win and loss art
+    win_banner_file: Option<PathBuf>,
+    loss_banner_file: Option<PathBuf>,
+    // where the board sits in the terminal: "left", "center" or "right"
+    board_alignment: String,
+}
 
-        if self.tries > self.max_tries {
-            return Err(Errors::MaximumTries(self.word.clone(), self.guesses.clone()));
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            banner: "R U D L E".to_string(),
+            selection_prompt: "Selection > ".to_string(),
+            option_prompt: "Option > ".to_string(),
+            guess_prompt: "Guess > ".to_string(),
+            end_banners: "classic".to_string(),
+            win_banner_file: None,
+            loss_banner_file: None,
+            board_alignment: "left".to_string(),
         }
-        
-        if self.hard && self.guesses.len() != 0 { // check if in hard mode and if already guessed a word
-            let last = self.guesses.last().unwrap();
-            let correct_letter_poses = last.iter().enumerate().filter(|(_, c)| {
-                match c {
-                    Guess::Correct(_) => true,
-                    _ => false,
-                }
-            }).map(|(i, c)| (i,c.get_letter())).collect::<Vec<(usize, char)>>();
-            let missed_letters = last.iter().filter(|c| {
-                match c {
-                    Guess::Missed(_) => true,
-                    _ => false,
-                }
-            }).map(|i| i.get_letter()).collect::<Vec<char>>();
-            let collected_chars = input.chars().collect::<Vec<char>>();
-            if !(correct_letter_poses.len() == 0) {
-                for (i, c) in correct_letter_poses {
-                    if let Some(ch) = collected_chars.get(i) {
-                        if ch != &c {
-                            return Err(Errors::InvalidWordInHardMode);
-                        }
-                    } else {
-                        return Err(Errors::InvalidWordInHardMode);
-                    }
-                }
-                for c in missed_letters {
-                    if !(collected_chars.contains(&c)) {
-                        return Err(Errors::InvalidWordInHardMode);
-                    }
-                }
+    }
+}
+
+impl UiConfig {
+    /// Puts the default back in place of any empty string, with a warning for each.
+    fn validated(mut self) -> (Self, Vec<String>) {
+        let defaults = Self::default();
+        let mut warnings = Vec::new();
+        for (name, value, default) in [
+            ("banner", &mut self.banner, defaults.banner),
+            ("selection_prompt", &mut self.selection_prompt, defaults.selection_prompt),
+            ("option_prompt", &mut self.option_prompt, defaults.option_prompt),
+            ("guess_prompt", &mut self.guess_prompt, defaults.guess_prompt),
+        ] {
+            if value.trim().is_empty() {
+                warnings.push(format!("The {} in the config is empty, using the default", name));
+                *value = default;
             }
         }
-    
-        let mut guesses = vec![Guess::Incorrect('_'); input.len()];
-        let mut correct_letters = 0;
-        let mut cloned_word = self.letter_counts.clone();
-    
-        for (i, letter) in input.chars().enumerate() {
-            if self.word.chars().nth(i).unwrap() == letter {
-                guesses[i] = Guess::Correct(letter);
-                correct_letters += 1;
-                cloned_word.entry(letter).and_modify(|x| *x -= 1);
-            }
+        if !banners::STYLES.contains(&self.end_banners.as_str()) {
+            warnings.push(format!(
+                "Unknown end_banners {:?} in the config (expected one of {}), using classic",
+                self.end_banners,
+                banners::STYLES.join(", ")
+            ));
+            self.end_banners = "classic".to_string();
         }
-    
-        for (i, letter) in input.chars().enumerate() {
-            if guesses[i] == Guess::Incorrect('_') { // Only check remaining letters
-                if self.word.contains(letter) && *cloned_word.entry(letter).or_insert(0) > 0 {
-                    guesses[i] = Guess::Missed(letter);
-                    cloned_word.entry(letter).and_modify(|x| *x -= 1);
-                } else {
-                    guesses[i] = Guess::Incorrect(letter);
+        if !BOARD_ALIGNMENTS.contains(&self.board_alignment.as_str()) {
+            warnings.push(format!(
+                "Unknown board_alignment {:?} in the config (expected one of {}), using left",
+                self.board_alignment,
+                BOARD_ALIGNMENTS.join(", ")
+            ));
+            self.board_alignment = "left".to_string();
+        }
+        for (name, file) in [
+            ("win_banner_file", &mut self.win_banner_file),
+            ("loss_banner_file", &mut self.loss_banner_file),
+        ] {
+            if let Some(path) = file {
+                if let Err(e) = std::fs::read_to_string(&*path) {
+                    warnings.push(format!("Can't read the {} {} ({}), using the built-in one", name, path.display(), e));
+                    *file = None;
                 }
             }
         }
-    
-        self.guesses.push(guesses.clone());
-        self.tries += 1;
-    
-        if correct_letters == self.word.len() {
-            self.playing = false;
-            let cloned_guesses = self.guesses.clone();
-            let tries = self.tries;
-            let max_tries = self.max_tries;
-            self.reset();
-            return Err(Errors::GameEndedWin(tries, max_tries, cloned_guesses));
+        (self, warnings)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_hints: 1,
+            guess_filter: None,
+            alphabet: "abcdefghijklmnopqrstuvwxyz".to_string(),
+            forfeit_is_loss: true,
+            reveal_pause: true,
+            community_log: None,
+            auto_save: false,
+            show_nearest_guess: false,
+            loss_reveal_delay_ms: 0,
+            ui: UiConfig::default(),
+            scoring: ScoringConfig::default(),
         }
-        Ok(guesses)
     }
-    
-    fn reset(&mut self) {
-        self.tries = 1;
-        self.guesses = Vec::new();
-        self.letter_counts = HashMap::new();
-        self.word = "".to_string();
+}
+
+impl Config {
+    fn path() -> Option<PathBuf> {
+        paths::config_dir().map(|dir| dir.join("config.json"))
+    }
+
+    /// Also returns a warning if the file was corrupt and had to be set aside.
+    fn load() -> (Self, Option<String>) {
+        Self::path().map(|path| load_json(&path)).unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        save_json(&Self::path().ok_or("no config directory available")?, self)
     }
 }
 
-#[derive(Debug, Clone)]
-enum Errors {
-    NoWordFound,
-    WordLengthNotEqualsToGuessWord,
-    InvalidWordInHardMode,
-    MaximumTries(String,Vec<Vec<Guess>>),
-    GameEndedWin(u64, u64, Vec<Vec<Guess>>)
+/// Every finished game, oldest first, stored as JSON in the user's data directory.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+struct Stats {
+    games: Vec<GameRecord>,
+    // fewest tries (then fastest time) of every word that was won
+    best: HashMap<String, PersonalBest>,
+    // numbers of the daily puzzles that were played to the end
+    dailies: Vec<i64>,
 }
 
-impl ToString for Errors {
-    fn to_string(&self) -> String {
-        match self {
-            Errors::NoWordFound => "No word found".to_string(),
-            Errors::WordLengthNotEqualsToGuessWord => {
-                "Word length does not match guess word length".to_string()
-            }
-            Errors::InvalidWordInHardMode => "Invalid word in hard mode".to_string(),
-            Errors::MaximumTries(_, _) => "Maximum tries reached".to_string(),
-            Errors::GameEndedWin(_,_,_) => "Game ended with a win, please restart the game".to_string(),
-        }
-    }
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct PersonalBest {
+    tries: u64,
+    seconds: u64,
 }
 
-fn main() {
-    better_panic::Settings::new()
-        .lineno_suffix(true)
-        .verbosity(better_panic::Verbosity::Full)
-        .install();
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+struct GameRecord {
+    word: String,
+    won: bool,
+    tries: u64,
+    seconds: u64,
+}
 
-    let cli = Cli::parse();
-    let mut game = Game::new(Dictionary::default(), cli.hard);
-    if cli.format_json {
-        let mut file = File::open("words.json").unwrap();
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).unwrap();
-        let words: HashMap<String, u8> = serde_json::from_str(&contents).unwrap();
-        let mut formatted = Vec::new();
-        for (word, _) in words {
-            if word.len() == 5 {
-                formatted.push(word);
-            }
-        }
-        serde_json::to_writer_pretty(File::create("words.txt").unwrap(), &formatted).unwrap();
-        return;
+impl Stats {
+    fn path() -> Option<PathBuf> {
+        paths::data_dir().map(|dir| dir.join("stats.json"))
     }
 
-    if let Some(path) = cli.word_dictionary {
-        game.dictionary
-            .load(path, cli.append)
-            .expect("Failed to load additonal word dictionary");
+    /// Also returns a warning if the file was corrupt and had to be set aside.
+    fn load() -> (Self, Option<String>) {
+        Self::path().map(|path| load_json(&path)).unwrap_or_default()
     }
-    clearscreen::clear().ok();
-    help();
-    loop {
-        let a = input(Some("Selection > "));
-        if a.to_lowercase() == "help" {
-            help();
-        } else if a.to_lowercase() == "play" {
-            play(&mut game);
-        } else if a.to_lowercase() == "options" {
-            options(&mut game);
-        } else if a.to_lowercase() == "exit" {
-            break;
-        } else {
-            println!("No options found");
+
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        save_json(&Self::path().ok_or("no data directory available")?, self)
+    }
+
+    /// Average tries and seconds over every won game, `None` if nothing was won yet.
+    fn win_averages(&self) -> Option<(f64, f64)> {
+        let wins = self.games.iter().filter(|g| g.won).collect::<Vec<&GameRecord>>();
+        if wins.is_empty() {
+            return None;
         }
+        let tries = wins.iter().map(|g| g.tries).sum::<u64>() as f64 / wins.len() as f64;
+        let seconds = wins.iter().map(|g| g.seconds).sum::<u64>() as f64 / wins.len() as f64;
+        Some((tries, seconds))
     }
-}
 
-fn options(game: &mut Game) {
-    loop {
-        clearscreen::clear().ok();
-        println!("{color_cyan}R U D L E{color_reset}");
-        println!("Options:");
-        println!(
-            "1. Append/Replace to word list ({} words)",
-            game.dictionary.words.len()
-        );
-        println!("2. Hard mode (yellow/green letters will need to be used on next guesses and green letters must stay where they are) ({})", if game.hard { "on" } else { "off" });
-        println!("3. Tries ({} tries)", game.max_tries);
-        println!("4. Exit");
+    /// The first daily puzzle after `from` that wasn't played yet, as long as it's not past `today`.
+    fn next_unplayed_daily(&self, from: i64, today: i64) -> Option<i64> {
+        (from + 1..=today).find(|n| !self.dailies.contains(n))
+    }
 
-        let ask = input(Some("Option > "));
-        if ask.to_lowercase() == "1" {
-            let append_or_replace =
-                match input(Some("Append or Replace to word list? (True/False) > "))
-                    .to_lowercase()
-                    .as_str()
-                {
-                    "true" => true,
-                    "false" => false,
-                    _ => false,
-                };
-            loop {
-                let option = input(Some("Append to word list (type q) (File path required) > "));
-                if option.to_lowercase() == "q" {
-                    break;
-                }
-                match game
-                    .dictionary
-                    .load(PathBuf::from(option), append_or_replace)
-                {
-                    Ok(()) => {
-                        println!("{color_green}Loaded Successfully.{color_reset}");
-                        break;
-                    }
-                    Err(e) => {
-                        println!(
-                            "{color_red}Failed to load.{color_reset} ({})",
-                            e.to_string()
-                        )
-                    }
-                }
+    /// Keeps `record` as the best of its word if it was won in fewer tries, or as fast with the same
+    /// tries. Returns whether it became the best.
+    fn update_best(&mut self, record: &GameRecord) -> bool {
+        if !record.won {
+            return false;
+        }
+        let candidate = PersonalBest {
+            tries: record.tries,
+            seconds: record.seconds,
+        };
+        match self.best.get(&record.word) {
+            Some(best) if (best.tries, best.seconds) <= (candidate.tries, candidate.seconds) => false,
+            _ => {
+                self.best.insert(record.word.clone(), candidate);
+                true
             }
-        } else if ask.to_lowercase() == "2" {
-            game.hard = !game.hard;
-            println!("Hard mode is now {}", if game.hard { "on" } else { "off" });
-        } else if ask.to_lowercase() == "3" {
-            let tries = match input(Some("Tries (type q) > ")).to_lowercase().as_str() {
-                "q" => 5,
-                _ => input(Some("Tries (type q) > ")).parse::<u64>().unwrap(),
-            };
-            game.max_tries = tries;
-            println!("Tries is now {}", tries);
-        } else {
-            break;
         }
     }
-}
 
-fn show_text(game: &Game) {
-    if cfg!(debug_assertions) {
-        println!("{color_cyan}R U D L E (Word is {}){color_reset}", game.word);
-    } else {
-        println!("{color_cyan}R U D L E (Word is {} characters long) {color_reset}", game.word.len());
+    /// Personal bests sorted by fewest tries, then fastest time.
+    fn leaderboard(&self) -> Vec<(&String, &PersonalBest)> {
+        let mut entries = self.best.iter().collect::<Vec<(&String, &PersonalBest)>>();
+        entries.sort_by(|a, b| (a.1.tries, a.1.seconds, a.0).cmp(&(b.1.tries, b.1.seconds, b.0)));
+        entries
     }
-    println!(
-        "{}",
-        game.guesses
-            .iter()
-            .map(|i| {
-                i.iter()
-                    .map(|j| match j {
-                        Guess::Correct(letter) => {
-                            format!("{bg_green}{color_black}{}{color_reset}{bg_reset}", letter)
-                        }
-                        Guess::Incorrect(letter) => {
-                            format!("{bg_red}{color_black}{}{color_reset}{bg_reset}", letter)
-                        }
-                        Guess::Missed(letter) => {
-                            format!("{bg_yellow}{color_black}{}{color_reset}{bg_reset}", letter)
-                        }
-                    })
-                    .collect()
+
+    /// The wins in a row at the end of the history and the most wins in a row ever.
+    fn streaks(&self) -> (usize, usize) {
+        let mut current = 0;
+        let mut longest = 0;
+        for game in &self.games {
+            current = if game.won { current + 1 } else { 0 };
+            longest = longest.max(current);
+        }
+        (current, longest)
+    }
+
+    /// How many games were won in each number of tries, from 1 to the most tries a win took.
+    fn tries_distribution(&self) -> Vec<(String, usize)> {
+        let most = self.games.iter().filter(|g| g.won).map(|g| g.tries).max().unwrap_or(0);
+        (1..=most)
+            .map(|tries| {
+                let wins = self.games.iter().filter(|g| g.won && g.tries == tries).count();
+                (tries.to_string(), wins)
             })
-            .collect::<Vec<Vec<String>>>()
+            .collect()
+    }
+
+    /// How many times each word was lost.
+    fn losses(&self) -> HashMap<String, u64> {
+        let mut losses = HashMap::new();
+        for game in self.games.iter().filter(|g| !g.won) {
+            *losses.entry(game.word.clone()).or_insert(0) += 1;
+        }
+        losses
+    }
+
+    /// Fastest win on `word`, in seconds.
+    fn best_time(&self, word: &str) -> Option<u64> {
+        self.games
             .iter()
-            .map(|i| { i.join(" ") })
-            .collect::<Vec<String>>()
-            .join("\n\n")
-    );
+            .filter(|g| g.won && g.word == word)
+            .map(|g| g.seconds)
+            .min()
+    }
 }
 
-fn play(game: &mut Game) {
-    game.play();
-    clearscreen::clear().ok();
-    loop {
-        if cfg!(debug_assertions) {
-            println!("{color_cyan}R U D L E (Word is {}) (Tries: {}/{} Tries{}){color_reset}", game.word, game.tries, game.max_tries, if game.hard { " (Hard Mode)" } else { "" });
-        } else {
-            println!("{color_cyan}R U D L E (Word is {} characters long) (Tries: {}/{} Tries{}){color_reset}", game.word.len(), game.tries, game.max_tries, if game.hard { " (Hard Mode)" } else { "" });
+/// A game written to disk with `save`, so it can be watched again with `replay`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+struct SavedGame {
+    word: String,
+    #[serde(with = "guess_rows")]
+    guesses: Vec<Vec<Guess>>,
+}
+
+// where files are kept, following the conventions of the platform (XDG directories on Linux)
+mod paths {
+    use std::path::PathBuf;
+
+    fn project() -> Option<directories::ProjectDirs> {
+        directories::ProjectDirs::from("", "", "rudle")
+    }
+
+    /// Creates `dir` if it's missing, a failure shows up later when something is written there.
+    fn created(dir: PathBuf) -> PathBuf {
+        std::fs::create_dir_all(&dir).ok();
+        dir
+    }
+
+    /// `own` if it's set, else `RUDLE_CONFIG_DIR` so one variable keeps every file of a test run
+    /// out of the user's directories, else the directory of the platform.
+    pub fn resolve(
+        own: Option<std::ffi::OsString>,
+        config: Option<std::ffi::OsString>,
+        platform: Option<PathBuf>,
+    ) -> Option<PathBuf> {
+        own.or(config).map(PathBuf::from).or(platform)
+    }
+
+    fn dir(variable: Option<&str>, platform: impl Fn(&directories::ProjectDirs) -> PathBuf) -> Option<PathBuf> {
+        let own = variable.and_then(std::env::var_os);
+        resolve(own, std::env::var_os("RUDLE_CONFIG_DIR"), project().map(|p| platform(&p))).map(created)
+    }
+
+    /// The config directory, `RUDLE_CONFIG_DIR` replaces it (handy for tests).
+    pub fn config_dir() -> Option<PathBuf> {
+        dir(None, |p| p.config_dir().to_path_buf())
+    }
+
+    /// Where the stats are kept, `RUDLE_DATA_DIR` replaces it.
+    pub fn data_dir() -> Option<PathBuf> {
+        dir(Some("RUDLE_DATA_DIR"), |p| p.data_dir().to_path_buf())
+    }
+
+    /// Where things that can be computed again are kept, safe to delete. `RUDLE_CACHE_DIR`
+    /// replaces it.
+    pub fn cache_dir() -> Option<PathBuf> {
+        dir(Some("RUDLE_CACHE_DIR"), |p| p.cache_dir().to_path_buf())
+    }
+}
+
+// rows are stored as text ("t:correct e:missed ...") so saved games can be read and edited by hand
+mod guess_rows {
+    use super::{parse_row, render_row_text, Guess};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(rows: &[Vec<Guess>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(rows.iter().map(|row| render_row_text(row)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<Guess>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|row| parse_row(row).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+// ascii art printed when a game is won or lost
+mod banners {
+    use super::UiConfig;
+
+    pub const STYLES: [&str; 3] = ["classic", "small", "off"];
+
+    const CLASSIC_WIN: &str = r"
+ __   __                     _         _
+ \ \ / /__  _   _  __      _(_)_ __   | |
+  \ V / _ \| | | | \ \ /\ / / | '_ \  | |
+   | | (_) | |_| |  \ V  V /| | | | | |_|
+   |_|\___/ \__,_|   \_/\_/ |_|_| |_| (_)
+";
+
+    const CLASSIC_LOSS: &str = r"
+  _   _            _     _   _
+ | \ | | _____  __| |_  | |_(_)_ __ ___   ___
+ |  \| |/ _ \ \/ /| __| | __| | '_ ` _ \ / _ \
+ | |\  |  __/>  < | |_  | |_| | | | | | |  __/
+ |_| \_|\___/_/\_\ \__|  \__|_|_| |_| |_|\___|
+";
+
+    const SMALL_WIN: &str = "\\o/ Nice one! \\o/";
+
+    const SMALL_LOSS: &str = "(._.) So close, better luck next time.";
+
+    /// The art for the end of a game, the configured file wins over the built-in style.
+    pub fn end_banner(ui: &UiConfig, won: bool) -> Option<String> {
+        let file = if won { &ui.win_banner_file } else { &ui.loss_banner_file };
+        if let Some(contents) = file.as_ref().and_then(|path| std::fs::read_to_string(path).ok()) {
+            return Some(contents);
         }
-        let input = input(Some("Guess > "));
-        let guesses = game.determine_guess(input);
-        match guesses {
-            Ok(_) => {
-                clearscreen::clear().ok();
-                show_text(game);
-                println!();
+        match (ui.end_banners.as_str(), won) {
+            ("classic", true) => Some(CLASSIC_WIN.to_string()),
+            ("classic", false) => Some(CLASSIC_LOSS.to_string()),
+            ("small", true) => Some(SMALL_WIN.to_string()),
+            ("small", false) => Some(SMALL_LOSS.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Meanings of the answers loaded with `--definitions`, a JSON map of word to definition.
+#[derive(Debug, Clone, Default)]
+struct Definitions(HashMap<String, String>);
+
+impl Definitions {
+    fn load(path: &PathBuf) -> Result<Self, Box<dyn Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        let definitions: HashMap<String, String> = serde_json::from_str(&contents)
+            .map_err(|e| format!("expected a map of words to their definition ({})", e))?;
+        Ok(Self(definitions.into_iter().map(|(word, definition)| (word.to_lowercase(), definition)).collect()))
+    }
+
+    fn get(&self, word: &str) -> Option<&str> {
+        self.0.get(&word.to_lowercase()).map(String::as_str)
+    }
+}
+
+/// The game in progress, written after every turn with `auto_save` in the config so it can be
+/// resumed after quitting in any way (even Ctrl-C) and removed once the game is over.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+struct AutoSave {
+    word: String,
+    #[serde(with = "guess_rows")]
+    guesses: Vec<Vec<Guess>>,
+    tries: u64,
+    max_tries: u64,
+    hard: bool,
+    hints: Vec<(usize, char)>,
+    // name of the dictionary that was active, the answer may not be in another one
+    dictionary: String,
+    #[serde(default)]
+    mulligan_used: bool,
+    // hints taken from the budget, the free ones in `hints` don't count (missing in older saves)
+    #[serde(default)]
+    hints_used: Option<u64>,
+    #[serde(default)]
+    anagram_given: bool,
+}
+
+impl AutoSave {
+    fn path() -> Option<PathBuf> {
+        paths::data_dir().map(|dir| dir.join("autosave.json"))
+    }
+
+    fn of(game: &Game) -> Self {
+        Self {
+            word: game.word.clone(),
+            guesses: game.guesses.clone(),
+            tries: game.tries,
+            max_tries: game.max_tries,
+            hard: game.hard,
+            hints: game.hints.clone(),
+            dictionary: game.active_dictionary.clone(),
+            mulligan_used: game.mulligan_used,
+            hints_used: Some(game.hints_used),
+            anagram_given: game.anagram_given,
+        }
+    }
+
+    /// The saved game, `None` if there is none or it can't be read.
+    fn load() -> Option<Self> {
+        Self::load_from(&Self::path()?)
+    }
+
+    fn load_from(path: &PathBuf) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str::<Self>(&contents).ok().filter(|saved| !saved.word.is_empty())
+    }
+
+    fn save(game: &Game) -> Result<(), Box<dyn Error>> {
+        save_json(&Self::path().ok_or("no data directory available")?, &Self::of(game))
+    }
+
+    fn clear() {
+        if let Some(path) = Self::path() {
+            std::fs::remove_file(path).ok();
+        }
+    }
+
+    /// Puts the saved game back into `game`, the clock starts again from now. The dictionary it was
+    /// played with is switched to if it's registered, and an answer that still isn't in the
+    /// dictionary is added so it can be guessed. Returns what was changed to make it fit.
+    fn restore(self, game: &mut Game) -> Option<String> {
+        let mut note = None;
+        if self.dictionary != game.active_dictionary && game.switch_dictionary(&self.dictionary) {
+            note = Some(format!("Switched to the {} dictionary the game was played with", self.dictionary));
+        }
+        if !game.is_known(&self.word) {
+            game.dictionary.words.push(self.word.clone());
+            note = Some(format!(
+                "The answer isn't in the {} dictionary, it was added for this session",
+                game.active_dictionary
+            ));
+        }
+        game.play_with(self.word);
+        game.turns = self.guesses.len() + usize::from(self.mulligan_used);
+        game.guesses = self.guesses;
+        game.tries = self.tries;
+        game.max_tries = self.max_tries;
+        game.hard = self.hard;
+        game.hints_used = self.hints_used.unwrap_or(self.hints.len() as u64);
+        game.hints = self.hints;
+        game.mulligan_used = self.mulligan_used;
+        game.anagram_given = self.anagram_given;
+        note
+    }
+}
+
+impl SavedGame {
+    fn load(path: &PathBuf) -> Result<Self, Box<dyn Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        let saved: Self = serde_json::from_str(&contents)
+            .map_err(|e| format!("the file is not a saved game ({})", e))?;
+        saved.validate()?;
+        Ok(saved)
+    }
+
+    fn save(&self, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+        save_json(path, self)
+    }
+
+    /// Checks that every row fits the answer, so a corrupted file is reported instead of drawn.
+    fn validate(&self) -> Result<(), String> {
+        let word = self.word.chars().collect::<Vec<char>>();
+        if word.is_empty() {
+            return Err("the saved game has no answer".to_string());
+        }
+        for (i, row) in self.guesses.iter().enumerate() {
+            if row.len() != word.len() {
+                return Err(format!("guess {} has {} letters but the answer has {}", i + 1, row.len(), word.len()));
             }
-            Err(e) => match e {
-                Errors::MaximumTries(word, guesses) => {
-                    show_text(game);
-                    println!("{color_yellow}Maximum tries reached, exiting...{color_reset}");
-                    println!("{color_red}The word was {}{color_reset}", word);
-                    println!("{color_green}Your accuracy is {}%{color_reset}", calculate_guess_accuracy(guesses) * 100.0);
-                    break;
-                },
-                Errors::GameEndedWin(tries, max_tries, guesses) => {
-                    println!("{color_green}You win!{color_reset}");
-                    println!("{bg_black}{color_bright_white} Took {}/{} tries.{color_reset}{bg_reset}", tries - 1, max_tries);
-                    println!("{color_green}Your accuracy is {}%{color_reset}", calculate_guess_accuracy(guesses.clone()) * 100.0);
-                    break;
-                }
-                _ => {
-                    println!("{color_red}ERROR: {}{color_reset}", e.to_string());
+            for (j, guess) in row.iter().enumerate() {
+                if let Guess::Correct(letter) = guess {
+                    if *letter != word[j] {
+                        return Err(format!("guess {} marks {} as correct but the answer has {} there", i + 1, letter, word[j]));
+                    }
                 }
-            },
+            }
         }
+        Ok(())
     }
 }
 
-fn calculate_guess_accuracy(guesses: Vec<Vec<Guess>>) -> f64 {
-    let mut points = 0.0;
-    let maximum_possible_point = guesses.first().unwrap().len() * 2; // 2 points per correct letter
-    for guess in &guesses {
-        for g in guess {
-            if let Guess::Correct(_) = g { points += 2.0; }
-            else if let Guess::Missed(_) = g { points += 1.0; }
-            else {
-                points -= 0.5;
-            }
+/// Games finished since the program started, kept apart from the saved stats.
+#[derive(Debug, Clone, Default)]
+struct Session {
+    games: Vec<GameRecord>,
+    // the answers in the order --tournament serves them, shuffled on the first game
+    tournament_words: Vec<String>,
+    // how many of them were played
+    tournament_round: usize,
+}
+
+impl Session {
+    fn summary(&self) -> Vec<String> {
+        if self.games.is_empty() {
+            return vec!["No games played this session.".to_string()];
+        }
+        let wins = self.games.iter().filter(|g| g.won).collect::<Vec<&GameRecord>>();
+        let mut lines = vec![format!("Games played: {}, won: {}", self.games.len(), wins.len())];
+        if wins.is_empty() {
+            return lines;
+        }
+        lines.push(format!(
+            "Average tries: {:.1}",
+            wins.iter().map(|g| g.tries).sum::<u64>() as f64 / wins.len() as f64
+        ));
+        // fewest tries wins, then the fastest
+        let best = wins.iter().min_by_key(|g| (g.tries, g.seconds)).unwrap();
+        lines.push(format!("Best game: {} in {} tries ({} seconds)", best.word, best.tries, best.seconds));
+        // a loss is worse than any win
+        let worst = self
+            .games
+            .iter()
+            .max_by_key(|g| (!g.won, g.tries, g.seconds))
+            .unwrap();
+        if worst.won {
+            lines.push(format!("Worst game: {} in {} tries ({} seconds)", worst.word, worst.tries, worst.seconds));
+        } else {
+            lines.push(format!("Worst game: {} (lost after {} tries)", worst.word, worst.tries));
         }
+        lines
     }
-    (points as f64 / guesses.len() as f64) / maximum_possible_point as f64
 }
 
-fn help() {
-    println!("{color_cyan}RUDLE{color_reset}");
-    println!(
-        "Welcome to {color_cyan}RUDLE{color_reset}! Please run the program with {bg_black}{color_bright_white}-h{color_reset}{bg_reset} for additional flags like hard mode! (or you can manually configure this inside the game)" 
-    );
-    println!(
-        "Any configurable options can be changed with {bg_black}{color_bright_white}options{color_reset}{bg_reset} as the input!"
-    );
-    println!(
-        "And when you are ready to play, type {bg_black}{color_bright_white}play{color_reset}{bg_reset}!"
-    );
+/// Reads `path`, falling back to the default when it doesn't exist. A file that can't be parsed is
+/// renamed to `<path>.bak` so the next save doesn't overwrite it, and a warning is returned.
+fn load_json<T: serde::de::DeserializeOwned + Default>(path: &PathBuf) -> (T, Option<String>) {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return (T::default(), None);
+    };
+    match serde_json::from_str(&contents) {
+        Ok(value) => (value, None),
+        Err(e) => {
+            let mut backup = path.clone().into_os_string();
+            backup.push(".bak");
+            let warning = match std::fs::rename(path, &backup) {
+                Ok(()) => format!(
+                    "{} was corrupt ({}), it was moved to {} and a new one will be created.",
+                    path.display(),
+                    e,
+                    PathBuf::from(&backup).display()
+                ),
+                Err(rename) => format!(
+                    "{} was corrupt ({}) and couldn't be backed up ({}).",
+                    path.display(),
+                    e,
+                    rename
+                ),
+            };
+            (T::default(), Some(warning))
+        }
+    }
 }
 
-fn input(ask: Option<&str>) -> String {
-    print!("{}", ask.unwrap_or(""));
-    std::io::stdout().flush().unwrap();
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    input.trim_end().to_string()
+fn save_json<T: serde::Serialize>(path: &PathBuf, value: &T) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_atomic(path, &serde_json::to_vec_pretty(value)?)
+}
+
+/// Adds `line` and a newline at the end of `path` in a single write, so lines of programs writing to
+/// the same file at once don't get mixed up.
+fn append_line(path: &PathBuf, line: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format!("{}\n", line).as_bytes())?;
+    Ok(())
+}
+
+/// Writes `bytes` to a temporary file next to `path` and renames it into place, so a crash midway
+/// leaves either the old file or the new one but never half of it.
+fn write_atomic(path: &PathBuf, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+    let mut name = path.file_name().ok_or("not a file path")?.to_os_string();
+    name.push(".tmp");
+    let temporary = path.with_file_name(name);
+    let mut file = File::create(&temporary)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    std::fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+// day (counted from the unix epoch) of daily puzzle #0, 2024-12-01
+const DAILY_EPOCH_DAY: i64 = 20058;
+
+/// How many periods of `seconds` went by between daily puzzle #0 and `now`, moved by `offset` periods.
+fn puzzle_index(now: SystemTime, seconds: u64, offset: i64) -> i64 {
+    let periods = now
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / seconds)
+        .unwrap_or(0) as i64;
+    periods - DAILY_EPOCH_DAY * (86400 / seconds as i64) + offset
+}
+
+/// Number of the daily puzzle for the (UTC) day of `now`, moved by `offset` days.
+fn daily_index(now: SystemTime, offset: i64) -> i64 {
+    puzzle_index(now, 86400, offset)
+}
+
+/// Number of the hourly puzzle for the (UTC) hour of `now`.
+fn hourly_index(now: SystemTime) -> i64 {
+    puzzle_index(now, 3600, 0)
+}
+
+// how many of the previous answers are avoided when drawing a new word
+const RECENT_WORDS_MEMORY: usize = 10;
+// the daily and hourly puzzles are shuffled with it, changing it changes every puzzle
+const PUZZLE_ORDER_SEED: u64 = 0x7275646c65;
+
+/// How hard a word is to find, from [`Dictionary::difficulty`].
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+/// Which words `--bias` favors when drawing a random answer.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum SelectionBias {
+    // words you lost on come back more often
+    Practice,
+    // words you lost on come back less often
+    Confidence,
+}
+
+/// Machine-readable ways to drive the game instead of the menus, picked with `--protocol`.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    // one JSON command per line on stdin, one JSON response per line on stdout
+    JsonLines,
+}
+
+/// What happens to a guess that is too long or too short.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum LengthPolicy {
+    // it is an error
+    Reject,
+    // extra letters are cut off, a short guess is still an error
+    Truncate,
+    // a short guess is filled up by repeating its last letter, extra letters are cut off
+    Pad,
+}
+
+/// How letters the guess has more copies of than the answer get colored.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ColoringMode {
+    // like Wordle, only as many yellows as the answer has copies left
+    Standard,
+    // every copy of a letter that is somewhere in the answer is yellow
+    AnyOccurrence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Guess {
+    Correct(char),
+    Incorrect(char),
+    Missed(char),
+}
+
+impl Guess {
+    fn get_letter(&self) -> char {
+        match self {
+            Guess::Correct(letter) => *letter,
+            Guess::Incorrect(letter) => *letter,
+            Guess::Missed(letter) => *letter,
+        }
+    }
+}
+
+// "t:correct", "e:missed" or "x:incorrect"
+impl fmt::Display for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Guess::Correct(_) => "correct",
+            Guess::Incorrect(_) => "incorrect",
+            Guess::Missed(_) => "missed",
+        };
+        write!(f, "{}:{}", self.get_letter(), status)
+    }
+}
+
+impl FromStr for Guess {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (letter, status) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("{} is not in the letter:status format", s))?;
+        let mut letters = letter.chars();
+        let (Some(letter), None) = (letters.next(), letters.next()) else {
+            return Err(format!("{} should be a single letter", letter));
+        };
+        match status.to_lowercase().as_str() {
+            "correct" => Ok(Guess::Correct(letter)),
+            "incorrect" => Ok(Guess::Incorrect(letter)),
+            "missed" => Ok(Guess::Missed(letter)),
+            _ => Err(format!("{} is not a known status (correct, incorrect or missed)", status)),
+        }
+    }
+}
+
+/// A row written with [`Guess`]'s `Display`, separated by spaces.
+fn render_row_text(row: &[Guess]) -> String {
+    row.iter().map(|g| g.to_string()).collect::<Vec<String>>().join(" ")
+}
+
+fn parse_row(text: &str) -> Result<Vec<Guess>, String> {
+    text.split_whitespace().map(Guess::from_str).collect()
+}
+
+impl Game {
+    fn new(dictionary: Dictionary, hard: bool) -> Self {
+        Self {
+            dictionary,
+            word: "".to_string(),
+            guesses: Vec::new(),
+            hard,
+            hardcore: false,
+            easy: false,
+            strict: true,
+            compact: false,
+            assist: false,
+            coloring_mode: ColoringMode::Standard,
+            playing: false,
+            tries: 1,
+            max_tries: 5,
+            recent_words: VecDeque::new(),
+            max_hints: Config::default().max_hints,
+            hints_used: 0,
+            hints: Vec::new(),
+            mercy: None,
+            no_progress_streak: 0,
+            mercy_hint: None,
+            fixed_word: None,
+            reveal: false,
+            ask_reveal: false,
+            letter_odds: false,
+            numbered: false,
+            bell: false,
+            show_timer: false,
+            blind: false,
+            progressive_hints: false,
+            export_html: None,
+            bias: None,
+            normalize_accents: false,
+            word_length: None,
+            required_letters: Vec::new(),
+            daily: None,
+            daily_number: None,
+            hourly: false,
+            hourly_number: None,
+            stats: Stats::default(),
+            started: None,
+            session: Session::default(),
+            config: Config::default(),
+            blocked_guesses: HashSet::new(),
+            theme: Theme::default(),
+            dictionaries: HashMap::new(),
+            active_dictionary: "default".to_string(),
+            max_repeats: None,
+            common: None,
+            coach: false,
+            length_policy: LengthPolicy::Reject,
+            scramble_keyboard: false,
+            keyboard_seed: 0,
+            team: false,
+            turns: 0,
+            pool_size: None,
+            show_pool: false,
+            anagram_given: false,
+            min_difficulty: None,
+            hidden_input: false,
+            fixed_letter: None,
+            alphabet_strip: false,
+            verbose: false,
+            tournament: None,
+            balanced_daily: false,
+            time_budget: None,
+            show_changes: false,
+            risky: false,
+            definitions: None,
+            entropy_gauge: false,
+            pattern_table: None,
+            mulligan: false,
+            mulligan_used: false,
+            discarded: None,
+            arcade: false,
+            tray: false,
+            fog: false,
+        }
+    }
+
+    fn apply_config(&mut self, config: &Config) {
+        self.max_hints = config.max_hints;
+        self.config = config.clone();
+    }
+
+    /// The config to save, with the settings changed in the options menu.
+    fn config(&self) -> Config {
+        Config {
+            max_hints: self.max_hints,
+            ..self.config.clone()
+        }
+    }
+
+    /// `target` (from RUDLE_TARGET) as the answer to pin, it has to be in the dictionary and as
+    /// long as `--length` if that was given.
+    fn target_word(&self, target: &str) -> Result<String, Errors> {
+        let target = target.trim().to_lowercase();
+        if !self.dictionary.have(&target) {
+            return Err(Errors::NoWordFound);
+        }
+        if self.word_length.is_some_and(|length| target.chars().count() != length) {
+            return Err(Errors::WordLengthNotEqualsToGuessWord);
+        }
+        Ok(target)
+    }
+
+    fn play(&mut self) -> Result<String, Errors> {
+        self.play_at(SystemTime::now())
+    }
+
+    /// Same as [`Game::play`] with `now` deciding the daily and hourly puzzles.
+    fn play_at(&mut self, now: SystemTime) -> Result<String, Errors> {
+        let mut daily_number = None;
+        let mut hourly_number = None;
+        let word = match (&self.fixed_word, self.daily) {
+            (Some(word), _) => word.clone(),
+            (None, Some(offset)) => {
+                let (number, word) = self.daily_puzzle(daily_index(now, offset))?;
+                daily_number = Some(number);
+                word
+            }
+            (None, None) if self.hourly => {
+                let (number, word) = self.hourly_puzzle(hourly_index(now))?;
+                hourly_number = Some(number);
+                word
+            }
+            (None, None) if self.tournament.is_some() => self.tournament_word()?,
+            (None, None) => match self.bias {
+                Some(bias) => self.answers().random_with_bias(&self.stats.losses(), bias, &self.recent_words)?,
+                None => self.answers().random_excluding(&self.recent_words)?,
+            },
+        };
+        self.recent_words.push_back(word.clone());
+        if self.recent_words.len() > RECENT_WORDS_MEMORY {
+            self.recent_words.pop_front();
+        }
+        let word = self.play_with(word);
+        self.daily_number = daily_number;
+        self.hourly_number = hourly_number;
+        if self.fixed_word.is_none() {
+            self.pool_size = Some(self.answers().words.len());
+        }
+        Ok(word)
+    }
+
+    /// The next answer of the `--tournament` order, starting over once every word was played.
+    fn tournament_word(&mut self) -> Result<String, Errors> {
+        if self.session.tournament_words.is_empty() {
+            self.session.tournament_words = self.answers().shuffled(self.tournament.unwrap_or(0));
+        }
+        let words = &self.session.tournament_words;
+        if words.is_empty() {
+            return Err(Errors::NoWordFound);
+        }
+        let word = words[self.session.tournament_round % words.len()].clone();
+        self.session.tournament_round += 1;
+        Ok(word)
+    }
+
+    /// The answer of daily puzzle number `index`.
+    fn daily_puzzle(&self, index: i64) -> Result<(i64, String), Errors> {
+        let answers = self.answers();
+        let word = if self.balanced_daily { answers.daily_balanced(index) } else { answers.daily(index) };
+        match word {
+            Some(word) => Ok((index, word)),
+            None => Err(Errors::NoDailyPuzzle(index, answers.words.len())),
+        }
+    }
+
+    /// The answer of hourly puzzle number `index`, there are more hours than words so the
+    /// [`Dictionary::puzzle_order`] is gone through again once every word was used.
+    fn hourly_puzzle(&self, index: i64) -> Result<(i64, String), Errors> {
+        let words = self.answers().puzzle_order();
+        if words.is_empty() {
+            return Err(Errors::NoWordFound);
+        }
+        let word = words[index.rem_euclid(words.len() as i64) as usize].clone();
+        Ok((index, word))
+    }
+
+    /// The words a random answer can be drawn from.
+    fn answers(&self) -> Dictionary {
+        self.answers_of_length(self.word_length)
+    }
+
+    /// The word lengths that have answers with the filters in use and how many, shortest first.
+    fn answer_lengths(&self) -> Vec<(usize, usize)> {
+        self.dictionary
+            .lengths()
+            .into_iter()
+            .map(|(length, _)| (length, self.answers_of_length(Some(length)).words.len()))
+            .filter(|(_, answers)| *answers > 0)
+            .collect()
+    }
+
+    /// Like [`Game::answers`] with `length` in place of the word length of the game.
+    fn answers_of_length(&self, length: Option<usize>) -> Dictionary {
+        let mut answers = self.dictionary.clone();
+        if let Some(length) = length {
+            answers = answers.with_length(length);
+        }
+        if !self.required_letters.is_empty() {
+            answers = answers.containing(&self.required_letters);
+        }
+        if let Some((position, letter)) = self.fixed_letter {
+            answers = answers.with_fixed(position, letter);
+        }
+        if let Some(n) = self.max_repeats {
+            answers = answers.filter_max_repeats(n);
+        }
+        if let Some(min) = self.min_difficulty {
+            answers = answers.with_min_difficulty(min);
+        }
+        // checked when --common is given, a dictionary switched to later may have no frequencies
+        if let Some(common) = self.common.and_then(|n| answers.top_common(n).ok()) {
+            answers = common;
+        }
+        answers
+    }
+
+    /// Starts a game with a word chosen by the caller instead of the dictionary (used by pass-and-play).
+    fn play_with(&mut self, word: String) -> String {
+        self.reset();
+        self.keyboard_seed = match self.tournament {
+            Some(seed) => phrase_seed(&format!("{} {}", seed, self.session.tournament_round)),
+            None => rand::random(),
+        };
+        self.playing = true;
+        self.started = Some(Instant::now());
+        self.word = word.clone();
+        word
+    }
+
+    /// Plays the current word again from an empty board.
+    fn restart_same_word(&mut self) {
+        let word = std::mem::take(&mut self.word);
+        self.play_with(word);
+    }
+
+    /// Records the game that just ended into the stats, returning what was recorded.
+    fn record(&mut self, won: bool) -> GameRecord {
+        let record = GameRecord {
+            word: self.word.clone(),
+            won,
+            tries: self.guesses.len() as u64,
+            seconds: self.started.map(|s| s.elapsed().as_secs()).unwrap_or(0),
+        };
+        self.stats.games.push(record.clone());
+        self.stats.update_best(&record);
+        if let Some(number) = self.daily_number.filter(|n| !self.stats.dailies.contains(n)) {
+            self.stats.dailies.push(number);
+        }
+        self.session.games.push(record.clone());
+        record
+    }
+
+    /// Ends a `--hardcore` game on a guess that was rejected and records it as a loss, other
+    /// errors and other modes leave the game going.
+    fn hardcore_loss(&mut self, error: &Errors) -> Option<GameRecord> {
+        if !self.hardcore || !error.is_rejected_guess() {
+            return None;
+        }
+        self.playing = false;
+        Some(self.record(false))
+    }
+
+    /// Letters given away for free at their positions: vowels in easy mode and any hints taken.
+    fn revealed_letters(&self) -> Vec<Option<char>> {
+        self.word
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if (self.easy && "aeiou".contains(c)) || self.hints.iter().any(|(p, _)| *p == i) {
+                    Some(c)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Reveals a letter that hasn't been found at its place yet, as long as there are hints left.
+    fn hint(&mut self) -> Result<(usize, char), Errors> {
+        if self.hints_used >= self.max_hints {
+            return Err(Errors::NoHintsLeft);
+        }
+        let hint = self.reveal_letter()?;
+        self.hints_used += 1;
+        Ok(hint)
+    }
+
+    /// The letters of the answer in alphabetical order, it costs a hint the first time it's asked
+    /// for. Refused when the board already leaves the answer as the only dictionary word (an answer
+    /// outside the dictionary can't be worked out from it, so it's always given).
+    fn anagram_hint(&mut self) -> Result<String, Errors> {
+        let mut letters = self.word.chars().collect::<Vec<char>>();
+        letters.sort();
+        let letters = letters.into_iter().collect::<String>();
+        if self.anagram_given {
+            return Ok(letters);
+        }
+        if self.is_known(&self.word) && self.possible_answers_iter().all(|w| w == self.word) {
+            return Err(Errors::NothingToHint);
+        }
+        if self.hints_used >= self.max_hints {
+            return Err(Errors::NoHintsLeft);
+        }
+        self.hints_used += 1;
+        self.anagram_given = true;
+        Ok(letters)
+    }
+
+    /// Gives away a letter for free once `mercy` guesses in a row found no green letter.
+    fn track_progress(&mut self, row: &[Guess]) {
+        self.mercy_hint = None;
+        if self.progressive_hint_due(row) {
+            self.mercy_hint = self.reveal_letter().ok();
+            return;
+        }
+        let Some(threshold) = self.mercy else {
+            return;
+        };
+        if row.iter().any(|g| matches!(g, Guess::Correct(_))) {
+            self.no_progress_streak = 0;
+            return;
+        }
+        self.no_progress_streak += 1;
+        if self.no_progress_streak >= threshold {
+            self.no_progress_streak = 0;
+            self.mercy_hint = self.reveal_letter().ok();
+        }
+    }
+
+    /// With --progressive-hints a letter is given after every miss once half the tries are used,
+    /// until a single letter is left to find.
+    fn progressive_hint_due(&self, row: &[Guess]) -> bool {
+        if !self.progressive_hints || row.iter().all(|g| matches!(g, Guess::Correct(_))) {
+            return false;
+        }
+        let revealed = self.revealed_letters();
+        let unknown = (0..revealed.len())
+            .filter(|i| {
+                revealed[*i].is_none()
+                    && !self.guesses.iter().any(|row| matches!(row.get(*i), Some(Guess::Correct(_))))
+            })
+            .count();
+        self.guesses.len() as u64 * 2 >= self.max_tries && unknown > 1
+    }
+
+    fn reveal_letter(&mut self) -> Result<(usize, char), Errors> {
+        let revealed = self.revealed_letters();
+        let position = self.word.chars().enumerate().find(|(i, _)| {
+            revealed[*i].is_none()
+                && !self
+                    .guesses
+                    .iter()
+                    .any(|row| matches!(row.get(*i), Some(Guess::Correct(_))))
+        });
+        match position {
+            Some(hint) => {
+                self.hints.push(hint);
+                Ok(hint)
+            }
+            None => Err(Errors::NothingToHint),
+        }
+    }
+
+    fn mode_label(&self) -> String {
+        let mut label = if self.hard {
+            " (Hard Mode)".to_string()
+        } else if self.easy {
+            " (Easy Mode)".to_string()
+        } else {
+            "".to_string()
+        };
+        if self.hardcore {
+            label.push_str(" (Hardcore)");
+        }
+        if let Some(number) = self.daily_number {
+            label.push_str(&format!(" (Daily #{})", number));
+        }
+        if let Some(number) = self.hourly_number {
+            label.push_str(&format!(" (Hourly #{})", number));
+        }
+        label
+    }
+
+    /// " (Time: mm:ss)" for the header with --show-timer, it reads 00:00 until the first guess.
+    fn timer_label(&self) -> String {
+        if !self.show_timer {
+            return String::new();
+        }
+        let seconds = match self.started {
+            Some(started) if !self.guesses.is_empty() => started.elapsed().as_secs(),
+            _ => 0,
+        };
+        format!(" (Time: {:02}:{:02})", seconds / 60, seconds % 60)
+    }
+
+    /// What is left of the `--time-budget` once `elapsed` went by, `None` without a budget.
+    fn time_left(&self, elapsed: std::time::Duration) -> Option<std::time::Duration> {
+        self.time_budget
+            .map(|budget| std::time::Duration::from_secs(budget).saturating_sub(elapsed))
+    }
+
+    /// The time left so far in the game, counting the time spent typing.
+    fn time_left_now(&self) -> Option<std::time::Duration> {
+        self.time_left(self.started.map(|s| s.elapsed()).unwrap_or_default())
+    }
+
+    /// " (Time left: mm:ss)" for the header with --time-budget.
+    fn budget_label(&self) -> String {
+        match self.time_left_now() {
+            Some(left) => {
+                // rounded up, 00:00 only shows once the time is really up
+                let seconds = left.as_millis().div_ceil(1000);
+                format!(" (Time left: {:02}:{:02})", seconds / 60, seconds % 60)
+            }
+            None => String::new(),
+        }
+    }
+
+    /// The colors `guess` gets against `answer` with the coloring mode and accent folding of this game.
+    fn score(&self, answer: &str, guess: &str) -> Vec<Guess> {
+        score_with(self.coloring_mode, self.normalize_accents, answer, guess)
+    }
+
+    /// Dictionary words that would have given every row on the board exactly its colors, so they
+    /// can still be the answer. Borrows the words instead of copying them, for solvers to build on.
+    fn possible_answers_iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let length = self.word.chars().count();
+        self.dictionary
+            .words
+            .iter()
+            .filter(move |w| w.chars().count() == length && self.fits(w, &self.guesses))
+            .map(String::as_str)
+    }
+
+    /// Whether `word` would have given every one of `rows` exactly its colors with the scoring of
+    /// this game, so it could be the answer.
+    fn fits(&self, word: &str, rows: &[Vec<Guess>]) -> bool {
+        rows.iter().all(|row| {
+            let guess = row.iter().map(|g| g.get_letter()).collect::<String>();
+            word.chars().count() == row.len() && self.score(word, &guess) == *row
+        })
+    }
+
+    /// The words of `words` that [`Game::fits`] the rows.
+    fn candidates<'a>(&self, words: &'a [String], rows: &[Vec<Guess>]) -> Vec<&'a String> {
+        words.iter().filter(|w| self.fits(w, rows)).collect()
+    }
+
+    fn determine_guess(&mut self, input: String) -> Result<Vec<Guess>, Errors> {
+        let normalize_accents = self.normalize_accents;
+        // letters are compared through this, so "e" can match "é" when accents are normalized
+        let fold = |c: char| if normalize_accents { strip_accents(c) } else { c };
+        let word_chars = self.word.chars().collect::<Vec<char>>();
+        if input.chars().count() != word_chars.len() {
+            return Err(Errors::WordLengthNotEqualsToGuessWord);
+        }
+        if self.blocked_guesses.contains(&input.to_lowercase()) {
+            return Err(Errors::GuessNotAllowed);
+        }
+        let invalid = input
+            .chars()
+            .filter(|c| !self.in_alphabet(*c))
+            .collect::<HashSet<char>>();
+        if !invalid.is_empty() {
+            let mut invalid = invalid.into_iter().collect::<Vec<char>>();
+            invalid.sort();
+            return Err(Errors::InvalidCharacters(invalid));
+        }
+        let known = self.is_known(&input);
+        // --risky lets a word that isn't in the dictionary through for an extra try
+        if self.strict && !known && !self.risky {
+            return Err(Errors::NoWordFound);
+        }
+
+        if self.tries > self.max_tries {
+            self.playing = false;
+            return Err(Errors::MaximumTries(self.word.clone(), self.guesses.clone()));
+        }
+        
+        if self.hard && !self.guesses.is_empty() { // check if in hard mode and if already guessed a word
+            // only what the player was shown has to be followed, fog hides the yellows
+            let shown = self.shown_rows(&self.guesses);
+            let last = shown.last().unwrap();
+            let correct_letter_poses = last.iter().enumerate().filter(|(_, c)| {
+                matches!(c, Guess::Correct(_))
+            }).map(|(i, c)| (i,c.get_letter())).collect::<Vec<(usize, char)>>();
+            let missed_letters = last.iter().filter(|c| {
+                matches!(c, Guess::Missed(_))
+            }).map(|i| i.get_letter()).collect::<Vec<char>>();
+            let collected_chars = input.chars().collect::<Vec<char>>();
+            if !correct_letter_poses.is_empty() {
+                for (i, c) in correct_letter_poses {
+                    if let Some(ch) = collected_chars.get(i) {
+                        if fold(*ch) != fold(c) {
+                            return Err(Errors::InvalidWordInHardMode);
+                        }
+                    } else {
+                        return Err(Errors::InvalidWordInHardMode);
+                    }
+                }
+                for c in missed_letters {
+                    if !collected_chars.iter().any(|ch| fold(*ch) == fold(c)) {
+                        return Err(Errors::InvalidWordInHardMode);
+                    }
+                }
+            }
+        }
+    
+        let guesses = self.score(&self.word, &input);
+        let correct_letters = guesses.iter().filter(|g| matches!(g, Guess::Correct(_))).count();
+    
+        self.guesses.push(guesses.clone());
+        self.turns += 1;
+        self.tries += 1;
+        if self.risky && !known {
+            self.tries += 1;
+        }
+        self.track_progress(&guesses);
+    
+        if correct_letters == word_chars.len() {
+            self.playing = false;
+            return Err(Errors::GameEndedWin(self.tries, self.max_tries, self.guesses.clone()));
+        }
+        Ok(guesses)
+    }
+    
+    /// The team mode player whose turn it is, 1 or 2.
+    fn player(&self) -> usize {
+        self.turns % 2 + 1
+    }
+
+    /// Whether the latest guess was the first of a team mode round, so the board waits for the second.
+    fn mid_round(&self) -> bool {
+        self.team && self.turns % 2 == 1
+    }
+
+    /// Whether `letter` is in the alphabet of the config, ignoring accents when they are normalized.
+    fn in_alphabet(&self, letter: char) -> bool {
+        let fold = |c: char| if self.normalize_accents { strip_accents(c) } else { c };
+        self.config.alphabet.chars().any(|a| fold(a) == fold(letter))
+    }
+
+    /// [`Game::determine_guess`], handing the typed text back with the error when the guess itself
+    /// was rejected so it can be shown again to be fixed.
+    fn submit_guess(&mut self, input: String) -> Result<Vec<Guess>, (Errors, Option<String>)> {
+        let typed = input.clone();
+        self.determine_guess(input).map_err(|e| {
+            let typed = e.is_rejected_guess().then_some(typed);
+            (e, typed)
+        })
+    }
+
+    /// Takes back the latest guess and its try, once per game with `--mulligan`. Hard mode
+    /// follows the guess before it again since it only looks at the latest row.
+    fn mulligan(&mut self) -> Result<(), Errors> {
+        if self.mulligan_used {
+            return Err(Errors::MulliganUsed);
+        }
+        let Some(row) = self.guesses.pop() else {
+            return Err(Errors::NoGuessToTakeBack);
+        };
+        // a --risky guess outside the dictionary cost two tries
+        let word = row.iter().map(|g| g.get_letter()).collect::<String>();
+        self.tries -= if self.risky && !self.is_known(&word) { 2 } else { 1 };
+        self.mulligan_used = true;
+        self.discarded = Some(row);
+        Ok(())
+    }
+
+    /// The rows the accuracy is worked out from: `guesses` and the one taken back with the
+    /// mulligan, which still counts.
+    fn scored_rows(&self, guesses: &[Vec<Guess>]) -> Vec<Vec<Guess>> {
+        guesses.iter().chain(&self.discarded).cloned().collect()
+    }
+
+    /// `guesses` the way the player sees them: with `--fog` every yellow turns gray.
+    fn shown_rows(&self, guesses: &[Vec<Guess>]) -> Vec<Vec<Guess>> {
+        if !self.fog {
+            return guesses.to_vec();
+        }
+        guesses
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|g| match g {
+                        Guess::Missed(letter) => Guess::Incorrect(*letter),
+                        other => *other,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Whether `word` is in the dictionary (also without its accents when they are normalized).
+    fn is_known(&self, word: &str) -> bool {
+        self.dictionary.have(word) || (self.normalize_accents && self.dictionary.have_ignoring_accents(word))
+    }
+
+    /// Makes the dictionary registered as `name` the active one, the game in progress is dropped.
+    fn switch_dictionary(&mut self, name: &str) -> bool {
+        let Some(next) = self.dictionaries.remove(name) else {
+            return false;
+        };
+        let previous = std::mem::replace(&mut self.dictionary, next);
+        self.dictionaries
+            .insert(std::mem::replace(&mut self.active_dictionary, name.to_string()), previous);
+        self.reset();
+        self.playing = false;
+        // the tournament order is drawn again from the new answers
+        self.session.tournament_words.clear();
+        self.session.tournament_round = 0;
+        true
+    }
+
+    /// Every registered dictionary with its word count, sorted by name.
+    fn dictionary_names(&self) -> Vec<(String, usize)> {
+        let mut names = self
+            .dictionaries
+            .iter()
+            .map(|(name, dictionary)| (name.clone(), dictionary.words.len()))
+            .chain(std::iter::once((
+                self.active_dictionary.clone(),
+                self.dictionary.words.len(),
+            )))
+            .collect::<Vec<(String, usize)>>();
+        names.sort();
+        names
+    }
+
+    fn reset(&mut self) {
+        self.pool_size = None;
+        // an abandoned daily is over too, it isn't offered to be caught up on from
+        self.daily_number = None;
+        self.hourly_number = None;
+        self.anagram_given = false;
+        self.tries = 1;
+        self.guesses = Vec::new();
+        self.turns = 0;
+        self.word = "".to_string();
+        self.hints_used = 0;
+        self.hints = Vec::new();
+        self.no_progress_streak = 0;
+        self.mercy_hint = None;
+        self.mulligan_used = false;
+        self.discarded = None;
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Errors {
+    NoWordFound,
+    WordLengthNotEqualsToGuessWord,
+    InvalidWordInHardMode,
+    GuessNotAllowed,
+    // the characters of the guess that aren't in the alphabet
+    InvalidCharacters(Vec<char>),
+    NoHintsLeft,
+    NothingToHint,
+    MulliganUsed,
+    NoGuessToTakeBack,
+    NoDailyPuzzle(i64, usize),
+    MaximumTries(String,Vec<Vec<Guess>>),
+    GameEndedWin(u64, u64, Vec<Vec<Guess>>)
+}
+
+impl Errors {
+    /// Whether the guess itself was rejected (as opposed to the game ending or a command failing).
+    fn is_rejected_guess(&self) -> bool {
+        matches!(
+            self,
+            Errors::NoWordFound
+                | Errors::WordLengthNotEqualsToGuessWord
+                | Errors::InvalidWordInHardMode
+                | Errors::GuessNotAllowed
+                | Errors::InvalidCharacters(_)
+        )
+    }
+}
+
+impl fmt::Display for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Errors::NoWordFound => write!(f, "No word found"),
+            Errors::WordLengthNotEqualsToGuessWord => {
+                write!(f, "Word length does not match guess word length")
+            }
+            Errors::InvalidWordInHardMode => write!(f, "Invalid word in hard mode"),
+            Errors::GuessNotAllowed => write!(f, "That guess is not allowed, try another word"),
+            Errors::InvalidCharacters(characters) => write!(
+                f,
+                "Guesses can only use letters of the alphabet (found {})",
+                characters.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(" ")
+            ),
+            Errors::NoHintsLeft => write!(f, "No hints left for this game"),
+            Errors::NothingToHint => write!(f, "Every letter is already known"),
+            Errors::MulliganUsed => write!(f, "The mulligan of this game was already used"),
+            Errors::NoGuessToTakeBack => write!(f, "There is no guess to take back yet"),
+            Errors::NoDailyPuzzle(number, count) => {
+                write!(f, "There is no daily puzzle #{}, there are {} of them", number, count)
+            }
+            Errors::MaximumTries(_, _) => write!(f, "Maximum tries reached"),
+            Errors::GameEndedWin(_,_,_) => write!(f, "Game ended with a win, please restart the game"),
+        }
+    }
+}
+
+impl Error for Errors {}
+
+fn main() {
+    better_panic::Settings::new()
+        .lineno_suffix(true)
+        .verbosity(better_panic::Verbosity::Full)
+        .install();
+
+    let cli = Cli::parse();
+    let dictionary = match Dictionary::try_default() {
+        Ok(dictionary) => dictionary,
+        Err(e) => {
+            println!("{color_red}Failed to load the dictionary.{color_reset} ({})", e);
+            return;
+        }
+    };
+    let mut game = Game::new(dictionary, cli.hard);
+    // printed under the welcome text so clearing the screen doesn't hide them
+    let mut warnings: Vec<String> = Vec::new();
+    let mut loaded: Vec<String> = Vec::new();
+    if let Some(path) = &cli.attempts_file {
+        match File::open(path) {
+            Ok(file) => INPUT.lock().unwrap().file = Some(std::io::BufReader::new(file)),
+            Err(e) => warnings.push(format!("Failed to open the attempts file. ({})", e)),
+        }
+    }
+    let (mut config, config_warning) = Config::load();
+    warnings.extend(config_warning);
+    let (ui, ui_warnings) = config.ui.validated();
+    config.ui = ui;
+    warnings.extend(ui_warnings);
+    let (scoring, scoring_warning) = config.scoring.validated();
+    config.scoring = scoring;
+    warnings.extend(scoring_warning);
+    game.apply_config(&config);
+    if let Some(path) = &config.guess_filter {
+        match read_word_list(path) {
+            Ok(words) => game.blocked_guesses = words.into_iter().map(|w| w.to_lowercase()).collect(),
+            Err(e) => warnings.push(format!("Failed to load the guess filter. ({})", e)),
+        }
+    }
+    let (stats, stats_warning) = Stats::load();
+    game.stats = stats;
+    warnings.extend(stats_warning);
+    game.easy = cli.easy;
+    game.hardcore = cli.hardcore;
+    game.strict = !cli.free_play;
+    game.normalize_accents = cli.normalize_accents;
+    game.mercy = cli.mercy;
+    game.reveal = cli.reveal;
+    game.ask_reveal = cfg!(debug_assertions) && !cli.reveal;
+    game.compact = cli.compact;
+    game.assist = cli.assist;
+    game.coloring_mode = cli.coloring;
+    game.letter_odds = cli.letter_odds;
+    game.numbered = cli.numbered;
+    game.bell = cli.bell;
+    game.show_timer = cli.show_timer;
+    game.blind = cli.blind;
+    game.progressive_hints = cli.progressive_hints;
+    game.export_html = cli.export_html;
+    game.bias = cli.bias;
+    game.coach = cli.coach;
+    game.length_policy = cli.length_policy;
+    NO_CLEAR.store(cli.no_clear, std::sync::atomic::Ordering::Relaxed);
+    game.scramble_keyboard = cli.scramble_keyboard;
+    game.team = cli.team;
+    game.show_pool = cli.show_pool;
+    game.hidden_input = cli.hidden_input;
+    game.alphabet_strip = cli.alphabet_strip;
+    game.verbose = cli.verbose;
+    game.tournament = cli.tournament.or(cli.seed_phrase.as_deref().map(phrase_seed));
+    game.time_budget = cli.time_budget;
+    game.show_changes = cli.show_changes;
+    game.risky = cli.risky;
+    game.entropy_gauge = cli.entropy_gauge;
+    game.mulligan = cli.mulligan;
+    game.arcade = cli.arcade;
+    game.tray = cli.tray;
+    game.fog = cli.fog;
+    if cli.format_json {
+        if let Err(e) = format_json() {
+            println!("{color_red}Failed to format words.json.{color_reset} ({})", e);
+        }
+        return;
+    }
+
+    if let Some(path) = cli.word_dictionary {
+        game.dictionary
+            .load(path, cli.append)
+            .expect("Failed to load additonal word dictionary");
+    }
+    for entry in &cli.dicts {
+        let Some((name, path)) = entry.split_once('=') else {
+            println!("{color_red}Expected --dict NAME=PATH, got {}.{color_reset}", entry);
+            return;
+        };
+        let mut dictionary = Dictionary::new();
+        match dictionary.load(PathBuf::from(path), false) {
+            Ok(()) => {
+                loaded.push(format!("Loaded dictionary {} ({} words)", name, dictionary.words.len()));
+                game.dictionaries.insert(name.to_string(), dictionary);
+            }
+            Err(e) => warnings.push(format!("Failed to load dictionary {}. ({})", name, e)),
+        }
+    }
+    if let Some(word) = cli.fixed_word {
+        let word = word.to_lowercase();
+        let length = word.chars().count();
+        if !game.dictionary.contains_length(length) {
+            println!("{color_red}There are no {} letters words in the dictionary to match the fixed word.{color_reset}", length);
+            return;
+        }
+        // otherwise strict mode would refuse the answer itself as a guess
+        if !game.is_known(&word) {
+            game.dictionary.words.push(word.clone());
+            loaded.push("Added the fixed word to the dictionary so it can be guessed".to_string());
+        }
+        game.fixed_word = Some(word);
+    }
+    if let Some(path) = &cli.definitions {
+        match Definitions::load(path) {
+            Ok(definitions) => {
+                loaded.push(format!("Loaded {} definitions", definitions.0.len()));
+                game.definitions = Some(definitions);
+            }
+            Err(e) => warnings.push(format!("Failed to load the definitions. ({})", e)),
+        }
+    }
+    if let Some(path) = &cli.theme {
+        match Theme::load(path) {
+            Ok((theme, theme_warnings)) => {
+                warnings.extend(theme_warnings.into_iter().map(|w| format!("Theme: {}", w)));
+                game.theme = theme;
+            }
+            Err(e) => warnings.push(format!("Failed to load the theme. ({})", e)),
+        }
+    }
+    if let Some(length) = cli.length {
+        if !game.dictionary.contains_length(length) {
+            println!("{color_red}There are no {} letters words in the dictionary.{color_reset}", length);
+            return;
+        }
+        game.word_length = Some(length);
+    }
+    // for automated tests: pins the answer like --fixed-word, but it must be a real answer
+    if let Ok(target) = std::env::var("RUDLE_TARGET") {
+        match game.target_word(&target) {
+            Ok(word) => game.fixed_word = Some(word),
+            Err(Errors::WordLengthNotEqualsToGuessWord) => {
+                println!(
+                    "{color_red}RUDLE_TARGET ({}) isn't {} letters long.{color_reset}",
+                    target.trim(),
+                    game.word_length.unwrap_or_default()
+                );
+                return;
+            }
+            Err(_) => {
+                println!("{color_red}RUDLE_TARGET ({}) is not in the dictionary.{color_reset}", target.trim());
+                return;
+            }
+        }
+    }
+    if let Some(letters) = cli.contains {
+        game.required_letters = letters.to_lowercase().chars().collect();
+        if game.answers().words.is_empty() {
+            println!("{color_red}There are no words with all of the letters {}.{color_reset}", letters);
+            return;
+        }
+    }
+    if let Some(fixed) = &cli.fixed {
+        let parsed = fixed.split_once('=').and_then(|(position, letter)| {
+            let mut letters = letter.trim().chars();
+            match (position.trim().parse::<usize>(), letters.next(), letters.next()) {
+                (Ok(position), Some(letter), None) if position > 0 => Some((position, letter.to_ascii_lowercase())),
+                _ => None,
+            }
+        });
+        let Some((position, letter)) = parsed else {
+            println!("{color_red}Expected --fixed POSITION=LETTER (e.g. 5=y), got {}.{color_reset}", fixed);
+            return;
+        };
+        if let Some(length) = game.word_length.filter(|l| position > *l) {
+            println!("{color_red}Position {} is past the end of {} letters words.{color_reset}", position, length);
+            return;
+        }
+        game.fixed_letter = Some((position - 1, letter));
+        if game.answers().words.is_empty() {
+            println!("{color_red}There are no words with {} at position {}.{color_reset}", letter, position);
+            return;
+        }
+    }
+    if let Some(n) = cli.max_repeats {
+        game.max_repeats = Some(n);
+        if game.answers().words.is_empty() {
+            println!("{color_red}There are no words without a letter showing up more than {} times.{color_reset}", n);
+            return;
+        }
+    }
+    if let Some(min) = cli.min_difficulty {
+        game.min_difficulty = Some(min);
+        if game.answers().words.is_empty() {
+            println!("{color_red}There are no answers rated {:?} or harder.{color_reset}", min);
+            return;
+        }
+    }
+    if let Some(n) = cli.common {
+        if let Err(e) = game.answers().top_common(n) {
+            println!("{color_red}Can't use --common.{color_reset} ({})", e);
+            return;
+        }
+        game.common = Some(n);
+        if game.answers().words.is_empty() {
+            println!("{color_red}There are no answers left among the {} most common words.{color_reset}", n);
+            return;
+        }
+    }
+    game.balanced_daily = cli.balanced_daily;
+    if cli.daily || cli.daily_offset.is_some() || cli.balanced_daily {
+        let offset = cli.daily_offset.unwrap_or(0);
+        if let Err(e) = game.daily_puzzle(daily_index(SystemTime::now(), offset)) {
+            println!("{color_red}{}{color_reset}", e);
+            return;
+        }
+        game.daily = Some(offset);
+    }
+    game.hourly = cli.hourly;
+    if game.word_length.is_none() {
+        warnings.extend(game.dictionary.mixed_lengths_warning());
+    }
+    if cli.protocol == Some(Protocol::JsonLines) {
+        run_protocol(&mut game);
+        return;
+    }
+    clear_screen();
+    help(&game.config.ui);
+    for line in loaded {
+        println!("{}", line);
+    }
+    for warning in warnings {
+        println!("{color_yellow}{}{color_reset}", warning);
+    }
+    // a fixed word or RUDLE_TARGET already decided the answer and its length
+    let lengths = game.answer_lengths();
+    if game.word_length.is_none() && game.fixed_word.is_none() && lengths.len() > 1 {
+        println!("Answers of several lengths are loaded:");
+        for (length, words) in &lengths {
+            println!("- {} letters ({} words)", length, words);
+        }
+        loop {
+            let length = input(Some("Word length (empty for any) > "));
+            if length.is_empty() {
+                break;
+            }
+            match length.parse::<usize>() {
+                Ok(length) if lengths.iter().any(|(l, _)| *l == length) => {
+                    game.word_length = Some(length);
+                    break;
+                }
+                Ok(length) => println!("{color_red}There are no {} letters answers that fit the filters in use.{color_reset}", length),
+                Err(e) => println!("{color_red}Invalid number.{color_reset} ({})", e),
+            }
+        }
+    }
+    if let Some(saved) = AutoSave::load().filter(|_| game.config.auto_save) {
+        let prompt = format!("Resume your unfinished game ({}/{} tries used)? (Y/n) > ", saved.guesses.len(), saved.max_tries);
+        if input(Some(&prompt)).to_lowercase() == "n" {
+            AutoSave::clear();
+        } else {
+            if let Some(note) = saved.restore(&mut game) {
+                println!("{color_yellow}{}{color_reset}", note);
+            }
+            guess_loop(&mut game);
+        }
+    }
+    loop {
+        let a = input(Some(&game.config.ui.selection_prompt));
+        if a.to_lowercase() == "help" {
+            help(&game.config.ui);
+        } else if a.to_lowercase() == "play" {
+            play(&mut game);
+        } else if a.to_lowercase() == "versus" {
+            versus(&mut game);
+        } else if a.to_lowercase() == "options" {
+            options(&mut game);
+        } else if let Some(path) = a.strip_prefix("save ") {
+            save(&game, PathBuf::from(path.trim()));
+        } else if let Some(path) = a.strip_prefix("add-answer ") {
+            if !game.reveal {
+                println!("{color_red}Adding answers to a dictionary file needs --reveal.{color_reset}");
+            } else if game.word.is_empty() {
+                println!("{color_red}There is no answer to add yet.{color_reset}");
+            } else {
+                add_answer(&game, PathBuf::from(path.trim()));
+            }
+        } else if let Some(arguments) = a.strip_prefix("simulate").filter(|r| r.is_empty() || r.starts_with(' ')) {
+            simulate(&game, arguments);
+        } else if let Some(search) = a.strip_prefix("pick ") {
+            if game.reveal || cfg!(debug_assertions) {
+                pick(&mut game, search.trim());
+            } else {
+                println!("{color_red}Picking the answer is only for testing, run with --reveal to use it.{color_reset}");
+            }
+        } else if let Some(arguments) = a.strip_prefix("stats").filter(|r| r.is_empty() || r.starts_with(' ')) {
+            show_stats(&game.stats, arguments);
+        } else if a.to_lowercase() == "leaderboard" {
+            leaderboard(&game.stats);
+        } else if a.to_lowercase() == "affixes" {
+            affixes(&game);
+        } else if a.to_lowercase() == "check-dictionary" {
+            check_dictionary(&game);
+        } else if let Some(arguments) = a.strip_prefix("verify-share ") {
+            verify_share(&game, arguments);
+        } else if let Some(arguments) = a.strip_prefix("diff-dict ") {
+            diff_dict(arguments);
+        } else if let Some(arguments) = a.strip_prefix("replay ") {
+            replay(arguments, &game.theme);
+        } else if a.to_lowercase() == "exit" {
+            println!("{color_cyan}Session summary{color_reset}");
+            for line in game.session.summary() {
+                println!("{}", line);
+            }
+            break;
+        } else {
+            println!("No options found");
+        }
+    }
+}
+
+fn options(game: &mut Game) {
+    loop {
+        clear_screen();
+        println!("{color_cyan}{}{color_reset}", game.config.ui.banner);
+        println!("Options:");
+        println!(
+            "1. Append/Replace to word list ({} words)",
+            game.dictionary.words.len()
+        );
+        if game.word_length.is_none() {
+            if let Some(warning) = game.dictionary.mixed_lengths_warning() {
+                println!("   {color_yellow}{}{color_reset}", warning);
+            }
+        }
+        println!("2. Hard mode (yellow/green letters will need to be used on next guesses and green letters must stay where they are) ({})", if game.hard { "on" } else { "off" });
+        println!("3. Tries ({} tries)", game.max_tries);
+        println!("4. Compact board (only show the latest guess and the keyboard) ({})", if game.compact { "on" } else { "off" });
+        println!("5. Easy mode (vowels are revealed at the start, turns off hard mode) ({})", if game.easy { "on" } else { "off" });
+        println!("6. Hints per game ({} hints)", game.max_hints);
+        println!("7. Strict dictionary (guesses must be in the word list) ({})", if game.strict { "on" } else { "off" });
+        println!("8. Dictionary ({}, {} words)", game.active_dictionary, game.dictionary.words.len());
+        println!("9. Coloring of repeated letters ({})", match game.coloring_mode {
+            ColoringMode::Standard => "standard",
+            ColoringMode::AnyOccurrence => "any occurrence",
+        });
+        println!("10. Word length ({})", match game.word_length {
+            Some(length) => format!("{} letters", length),
+            None => "any".to_string(),
+        });
+        println!("11. Exit");
+
+        let ask = input(Some(&game.config.ui.option_prompt));
+        if ask.to_lowercase() == "1" {
+            let append_or_replace =
+                match input(Some("Append or Replace to word list? (True/False) > "))
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "true" => true,
+                    "false" => false,
+                    _ => false,
+                };
+            loop {
+                let option = input(Some("Append to word list (type q) (File path required) > "));
+                if option.to_lowercase() == "q" {
+                    break;
+                }
+                match game
+                    .dictionary
+                    .load(PathBuf::from(option), append_or_replace)
+                {
+                    Ok(()) => {
+                        println!("{color_green}Loaded Successfully.{color_reset}");
+                        break;
+                    }
+                    Err(e) => {
+                        println!(
+                            "{color_red}Failed to load.{color_reset} ({})",
+                            e
+                        )
+                    }
+                }
+            }
+        } else if ask.to_lowercase() == "2" {
+            game.hard = !game.hard;
+            if game.hard {
+                game.easy = false;
+            }
+            println!("Hard mode is now {}", if game.hard { "on" } else { "off" });
+        } else if ask.to_lowercase() == "3" {
+            let tries = match input(Some("Tries (type q) > ")).to_lowercase().as_str() {
+                "q" => 5,
+                _ => input(Some("Tries (type q) > ")).parse::<u64>().unwrap(),
+            };
+            game.max_tries = tries;
+            println!("Tries is now {}", tries);
+        } else if ask.to_lowercase() == "4" {
+            game.compact = !game.compact;
+            println!("Compact board is now {}", if game.compact { "on" } else { "off" });
+        } else if ask.to_lowercase() == "5" {
+            game.easy = !game.easy;
+            if game.easy {
+                game.hard = false;
+            }
+            println!("Easy mode is now {}", if game.easy { "on" } else { "off" });
+        } else if ask.to_lowercase() == "6" {
+            match input(Some("Hints per game > ")).parse::<u64>() {
+                Ok(hints) => {
+                    game.max_hints = hints;
+                    if let Err(e) = game.config().save() {
+                        println!("{color_red}Failed to save config.{color_reset} ({})", e);
+                    }
+                    println!("Hints per game is now {}", hints);
+                }
+                Err(e) => println!("{color_red}Invalid number.{color_reset} ({})", e),
+            }
+        } else if ask.to_lowercase() == "7" {
+            game.strict = !game.strict;
+            println!("Strict dictionary is now {}", if game.strict { "on" } else { "off" });
+        } else if ask.to_lowercase() == "8" {
+            for (name, words) in game.dictionary_names() {
+                println!("- {} ({} words)", name, words);
+            }
+            let name = input(Some("Dictionary name > "));
+            if name == game.active_dictionary {
+                println!("{} is already in use", name);
+            } else if game.switch_dictionary(&name) {
+                println!("Dictionary is now {}, the current game was reset", name);
+                if game.answers().words.is_empty() {
+                    println!("{color_yellow}None of its words fit the word length and filters in use, pick another length with option 10.{color_reset}");
+                }
+            } else {
+                println!("{color_red}There is no dictionary called {}.{color_reset}", name);
+            }
+        } else if ask.to_lowercase() == "9" {
+            game.coloring_mode = match game.coloring_mode {
+                ColoringMode::Standard => ColoringMode::AnyOccurrence,
+                ColoringMode::AnyOccurrence => ColoringMode::Standard,
+            };
+            println!("Coloring is now {}", match game.coloring_mode {
+                ColoringMode::Standard => "standard",
+                ColoringMode::AnyOccurrence => "any occurrence",
+            });
+        } else if ask.to_lowercase() == "10" {
+            for (length, words) in game.dictionary.lengths() {
+                println!("- {} letters ({} words)", length, words);
+            }
+            let length = input(Some("Word length (type any for every length) > "));
+            if length.to_lowercase() == "any" {
+                game.word_length = None;
+                println!("Word length is now any");
+                continue;
+            }
+            match length.parse::<usize>() {
+                Ok(length) if !game.answers_of_length(Some(length)).words.is_empty() => {
+                    game.word_length = Some(length);
+                    println!("Word length is now {} letters", length);
+                }
+                Ok(length) => println!("{color_red}There are no {} letters answers that fit the filters in use.{color_reset}", length),
+                Err(e) => println!("{color_red}Invalid number.{color_reset} ({})", e),
+            }
+        } else {
+            break;
+        }
+    }
+}
+
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// Foreground and background ANSI codes of a tile.
+#[derive(Debug, Clone, Copy)]
+struct TileColors {
+    fg: &'static str,
+    bg: &'static str,
+}
+
+/// Colors of every tile status, `--theme` loads them from a TOML file.
+#[derive(Debug, Clone, Copy)]
+struct Theme {
+    correct: TileColors,
+    missed: TileColors,
+    incorrect: TileColors,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            correct: TileColors { fg: color_black, bg: bg_green },
+            missed: TileColors { fg: color_black, bg: bg_yellow },
+            incorrect: TileColors { fg: color_black, bg: bg_red },
+        }
+    }
+}
+
+// [correct]
+// fg = "black"
+// bg = "green"
+#[derive(serde::Deserialize, Debug, Default)]
+#[serde(default)]
+struct ThemeFile {
+    correct: TileColorNames,
+    missed: TileColorNames,
+    incorrect: TileColorNames,
+}
+
+#[derive(serde::Deserialize, Debug, Default)]
+#[serde(default)]
+struct TileColorNames {
+    fg: Option<String>,
+    bg: Option<String>,
+}
+
+impl Theme {
+    /// Loads a theme file, colors that are missing or unknown keep their default.
+    /// Returns the theme and a warning for every unknown color name.
+    fn load(path: &PathBuf) -> Result<(Self, Vec<String>), Box<dyn Error>> {
+        let file: ThemeFile = toml::from_str(&std::fs::read_to_string(path)?)?;
+        let mut theme = Self::default();
+        let mut warnings = Vec::new();
+        for (status, names, colors) in [
+            ("correct", &file.correct, &mut theme.correct),
+            ("missed", &file.missed, &mut theme.missed),
+            ("incorrect", &file.incorrect, &mut theme.incorrect),
+        ] {
+            for (name, background, color) in [
+                (&names.fg, false, &mut colors.fg),
+                (&names.bg, true, &mut colors.bg),
+            ] {
+                let Some(name) = name else {
+                    continue;
+                };
+                match ansi_color(name, background) {
+                    Some(code) => *color = code,
+                    None => warnings.push(format!("unknown color {} for {}, using the default", name, status)),
+                }
+            }
+        }
+        Ok((theme, warnings))
+    }
+
+    fn colors(&self, guess: &Guess) -> TileColors {
+        match guess {
+            Guess::Correct(_) => self.correct,
+            Guess::Missed(_) => self.missed,
+            Guess::Incorrect(_) => self.incorrect,
+        }
+    }
+}
+
+fn ansi_color(name: &str, background: bool) -> Option<&'static str> {
+    let (fg, bg) = match name.to_lowercase().as_str() {
+        "black" => (color_black, bg_black),
+        "red" => (color_red, bg_red),
+        "green" => (color_green, bg_green),
+        "yellow" => (color_yellow, bg_yellow),
+        "blue" => (color_blue, bg_blue),
+        "magenta" => (color_magenta, bg_magenta),
+        "cyan" => (color_cyan, bg_cyan),
+        "white" => (color_white, bg_white),
+        "bright_black" => (color_bright_black, bg_bright_black),
+        "bright_red" => (color_bright_red, bg_bright_red),
+        "bright_green" => (color_bright_green, bg_bright_green),
+        "bright_yellow" => (color_bright_yellow, bg_bright_yellow),
+        "bright_blue" => (color_bright_blue, bg_bright_blue),
+        "bright_magenta" => (color_bright_magenta, bg_bright_magenta),
+        "bright_cyan" => (color_bright_cyan, bg_bright_cyan),
+        "bright_white" => (color_bright_white, bg_bright_white),
+        _ => return None,
+    };
+    Some(if background { bg } else { fg })
+}
+
+/// The board after a turn, or in blind mode only the summary of the latest row. Nothing is shown
+/// in the middle of a team mode round, the second guess comes first.
+fn show_turn(game: &Game) {
+    if game.mid_round() {
+        return;
+    }
+    if game.blind {
+        if let Some(row) = game.shown_rows(&game.guesses).last() {
+            println!("{}: {}", row.iter().map(|g| g.get_letter()).collect::<String>(), summarize_row(row));
+        }
+    } else {
+        clear_screen();
+        show_text(game);
+        println!();
+    }
+}
+
+fn show_text(game: &Game) {
+    println!("{}", align_to_terminal(&render_board(game, game.reveal), &game.config.ui.board_alignment));
+}
+
+/// [`align_block`] across the terminal, `text` as it is when its width is unknown.
+fn align_to_terminal(text: &str, alignment: &str) -> String {
+    match terminal_width() {
+        Some(width) => align_block(text, alignment, width),
+        None => text.to_string(),
+    }
+}
+
+const BOARD_ALIGNMENTS: [&str; 3] = ["left", "center", "right"];
+
+/// Columns of the terminal, from `COLUMNS` when the terminal can't be asked (piped output).
+fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| width as usize)
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+}
+
+/// How many columns `line` takes on screen, its color codes take none.
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip to the letter ending the escape sequence
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Moves every line of `text` by the same amount, so the widest one is centered or right aligned
+/// in `width` columns and the rows stay lined up.
+fn align_block(text: &str, alignment: &str, width: usize) -> String {
+    let widest = text.lines().map(visible_width).max().unwrap_or(0);
+    let padding = match alignment {
+        "center" => width.saturating_sub(widest) / 2,
+        "right" => width.saturating_sub(widest),
+        _ => 0,
+    };
+    text.lines()
+        .map(|line| if line.is_empty() { String::new() } else { " ".repeat(padding) + line })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The title and the board as `show_text` prints them, `reveal` puts the answer in the title.
+fn render_board(game: &Game, reveal: bool) -> String {
+    let mut lines = Vec::new();
+    if reveal {
+        lines.push(format!("{color_cyan}{} (Word is {}){color_reset}", game.config.ui.banner, game.word));
+    } else {
+        lines.push(format!("{color_cyan}{} (Word is {} characters long) {color_reset}", game.config.ui.banner, game.word.chars().count()));
+    }
+    let guesses = game.shown_rows(&game.guesses);
+    // "3: " in front of every row with --numbered, wide enough for the last try
+    let width = game.max_tries.max(guesses.len() as u64).to_string().len();
+    let prefix = |number: Option<usize>| match (game.numbered, number) {
+        (false, _) => String::new(),
+        (true, Some(number)) => format!("{:>width$}: ", number),
+        (true, None) => " ".repeat(width + 2),
+    };
+    // with --show-changes the latest row underlines what changed since the row before
+    let row = |i: usize| match i.checked_sub(1) {
+        Some(previous) if game.show_changes && i + 1 == guesses.len() => {
+            render_row_changes(&guesses[i], &guesses[previous], &game.theme)
+        }
+        _ => render_row(&guesses[i], &game.theme),
+    };
+    if game.easy || !game.hints.is_empty() {
+        lines.push(prefix(None) + &render_revealed(&game.revealed_letters(), &game.theme));
+        lines.push(String::new());
+    }
+    if game.compact {
+        if !guesses.is_empty() {
+            lines.push(prefix(Some(guesses.len())) + &row(guesses.len() - 1));
+        }
+        lines.push(String::new());
+        let layout = keyboard_layout(game.scramble_keyboard.then_some(game.keyboard_seed));
+        lines.push(render_keyboard(&guesses, &layout, &game.theme));
+    } else if game.show_changes {
+        let rows = (0..guesses.len()).map(|i| prefix(Some(i + 1)) + &row(i)).collect::<Vec<String>>();
+        lines.push(rows.join("\n\n"));
+    } else if game.numbered {
+        lines.push(render_numbered_rows(&guesses, &game.theme, width));
+    } else {
+        lines.push(render_rows(&guesses, &game.theme));
+    }
+    if game.alphabet_strip {
+        lines.push(String::new());
+        lines.push(alphabet_strip(&guesses, &game.config.alphabet));
+    }
+    if game.tray {
+        let tray = letter_tray(&guesses);
+        lines.push(String::new());
+        lines.push(format!(
+            "Tray: {}",
+            tray.iter().map(|c| c.to_uppercase().to_string()).collect::<Vec<String>>().join(" ")
+        ));
+    }
+    if game.assist {
+        let alphabet = game
+            .answers()
+            .words
+            .iter()
+            .flat_map(|w| w.chars())
+            .collect::<HashSet<char>>()
+            .into_iter()
+            .collect::<Vec<char>>();
+        lines.push(String::new());
+        for (i, letters) in letter_bank(game.word.chars().count(), &game.guesses, &alphabet)
+            .iter()
+            .enumerate()
+        {
+            lines.push(format!(
+                "{}: {}",
+                i + 1,
+                letters.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(" ")
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// `guesses` in the theme of the game, lined up like [`show_text`] lines up the board.
+fn show_board(game: &Game, guesses: &[Vec<Guess>]) {
+    let rows = if game.numbered {
+        render_numbered_rows(guesses, &game.theme, guesses.len().to_string().len())
+    } else {
+        render_rows(guesses, &game.theme)
+    };
+    println!("{}", align_to_terminal(&rows, &game.config.ui.board_alignment));
+}
+
+fn render_rows(guesses: &[Vec<Guess>], theme: &Theme) -> String {
+    guesses
+        .iter()
+        .map(|i| render_row(i, theme))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Like [`render_rows`] with the try number, right aligned to `width`, in front of every row.
+fn render_numbered_rows(guesses: &[Vec<Guess>], theme: &Theme, width: usize) -> String {
+    guesses
+        .iter()
+        .enumerate()
+        .map(|(i, row)| format!("{:>width$}: {}", i + 1, render_row(row, theme)))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+fn render_tile(guess: &Guess, theme: &Theme) -> String {
+    let colors = theme.colors(guess);
+    format!("{}{}{}{color_reset}{bg_reset}", colors.bg, colors.fg, guess.get_letter())
+}
+
+fn render_revealed(revealed: &[Option<char>], theme: &Theme) -> String {
+    revealed
+        .iter()
+        .map(|c| match c {
+            Some(letter) => render_tile(&Guess::Correct(*letter), theme),
+            None => "_".to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn render_row(row: &[Guess], theme: &Theme) -> String {
+    row.iter().map(|g| render_tile(g, theme)).collect::<Vec<String>>().join(" ")
+}
+
+/// Like [`render_row`] with the letters that aren't the same as in `previous` underlined.
+fn render_row_changes(row: &[Guess], previous: &[Guess], theme: &Theme) -> String {
+    row.iter()
+        .enumerate()
+        .map(|(i, g)| {
+            if previous.get(i).map(|p| p.get_letter()) == Some(g.get_letter()) {
+                render_tile(g, theme)
+            } else {
+                format!("{style_underline}{}{style_reset}", render_tile(g, theme))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Best known status of every guessed letter (green beats yellow beats red).
+fn keyboard_state(guesses: &[Vec<Guess>]) -> HashMap<char, Guess> {
+    let mut state: HashMap<char, Guess> = HashMap::new();
+    for guess in guesses.iter().flatten() {
+        let rank = |g: &Guess| match g {
+            Guess::Correct(_) => 2,
+            Guess::Missed(_) => 1,
+            Guess::Incorrect(_) => 0,
+        };
+        let entry = state.entry(guess.get_letter()).or_insert(*guess);
+        if rank(guess) > rank(entry) {
+            *entry = *guess;
+        }
+    }
+    state
+}
+
+/// The `--tournament` seed of a `--seed-phrase`, FNV-1a over its bytes so it stays the same
+/// across builds and platforms (unlike the std hasher).
+fn phrase_seed(phrase: &str) -> u64 {
+    phrase
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3))
+}
+
+/// The QWERTY rows, or with a seed the same letters shuffled the same way every time for that seed.
+fn keyboard_layout(seed: Option<u64>) -> Vec<String> {
+    let Some(seed) = seed else {
+        return KEYBOARD_ROWS.iter().map(|row| row.to_string()).collect();
+    };
+    let mut letters = KEYBOARD_ROWS.concat().chars().collect::<Vec<char>>();
+    rand::seq::SliceRandom::shuffle(
+        letters.as_mut_slice(),
+        &mut <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(seed),
+    );
+    let mut rest = letters.as_slice();
+    KEYBOARD_ROWS
+        .iter()
+        .map(|row| {
+            let (keys, tail) = rest.split_at(row.len());
+            rest = tail;
+            keys.iter().collect()
+        })
+        .collect()
+}
+
+fn render_keyboard(guesses: &[Vec<Guess>], layout: &[String], theme: &Theme) -> String {
+    let state = keyboard_state(guesses);
+    layout
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let keys = row
+                .chars()
+                .map(|c| match state.get(&c) {
+                    Some(guess) => render_tile(guess, theme),
+                    None => c.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(" ");
+            format!("{}{}", " ".repeat(i), keys)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn play(game: &mut Game) {
+    if game.ask_reveal {
+        game.reveal = input(Some("Reveal the answer for testing? (y/N) > ")).to_lowercase() == "y";
+    }
+    // catching up on past daily puzzles, the next one is offered until today's
+    let catching_up = game.daily.is_some_and(|offset| offset < 0);
+    loop {
+        if let Err(e) = game.play() {
+            println!("{color_red}ERROR: {}{color_reset}", e);
+            return;
+        }
+        guess_loop(game);
+        let Some(number) = game.daily_number.filter(|_| catching_up && !game.playing) else {
+            return;
+        };
+        let today = daily_index(SystemTime::now(), 0);
+        let Some(next) = game.stats.next_unplayed_daily(number, today) else {
+            println!("{color_green}You are all caught up on the daily puzzles.{color_reset}");
+            return;
+        };
+        if input(Some(&format!("Play daily #{} next? (y/N) > ", next))).to_lowercase() != "y" {
+            return;
+        }
+        game.daily = Some(next - today);
+    }
+}
+
+fn versus(game: &mut Game) {
+    clear_screen();
+    println!("{color_cyan}{} (Pass and Play){color_reset}", game.config.ui.banner);
+    println!("Player 1, pick a word for player 2 to guess. (It will not be shown while typing)");
+    let word = loop {
+        let secret = input_hidden("Secret word (type q) > ").trim().to_lowercase();
+        if secret == "q" {
+            return;
+        }
+        if secret.is_empty() || !secret.chars().all(|c| game.in_alphabet(c)) {
+            println!("{color_red}The word can only contain letters of the alphabet.{color_reset}");
+            continue;
+        }
+        let length = secret.chars().count();
+        if !game.dictionary.contains_length(length) {
+            println!("{color_red}There are no {} letters words in the dictionary.{color_reset}", length);
+            continue;
+        }
+        if !game.dictionary.have(&secret) {
+            let add = input(Some("That word is not in the dictionary, add it? (y/N) > "));
+            if add.to_lowercase() != "y" {
+                continue;
+            }
+            game.dictionary.words.push(secret.clone());
+        }
+        break secret;
+    };
+    game.play_with(word);
+    clear_screen();
+    input(Some("Pass it to player 2 and press enter to start guessing..."));
+    guess_loop(game);
+}
+
+fn add_answer(game: &Game, path: PathBuf) {
+    match append_word(&path, &game.word) {
+        Ok(true) => println!("{color_green}Added {} to {}.{color_reset}", game.word, path.display()),
+        Ok(false) => println!("{color_yellow}{} is already in {}.{color_reset}", game.word, path.display()),
+        Err(e) => println!("{color_red}Failed to add the answer.{color_reset} ({})", e),
+    }
+}
+
+fn save(game: &Game, path: PathBuf) {
+    if game.guesses.is_empty() {
+        println!("{color_red}There is no game to save yet.{color_reset}");
+        return;
+    }
+    if game.playing {
+        println!("{color_red}Finish the game before saving it.{color_reset}");
+        return;
+    }
+    let saved = SavedGame {
+        word: game.word.clone(),
+        guesses: game.guesses.clone(),
+    };
+    match saved.save(&path) {
+        Ok(()) => println!("{color_green}Saved the game to {}.{color_reset}", path.display()),
+        Err(e) => println!("{color_red}Failed to save the game.{color_reset} ({})", e),
+    }
+}
+
+// how many matches `pick` lists at most
+const PICK_RESULTS: usize = 20;
+
+/// Searches the dictionary for words containing `search` and plays the one that is chosen.
+fn pick(game: &mut Game, search: &str) {
+    let search = search.to_lowercase();
+    let matches = game
+        .dictionary
+        .words
+        .iter()
+        .filter(|w| w.contains(&search))
+        .cloned()
+        .collect::<Vec<String>>();
+    if matches.is_empty() {
+        println!("{color_yellow}No word contains {:?}.{color_reset}", search);
+        return;
+    }
+    for (i, word) in matches.iter().take(PICK_RESULTS).enumerate() {
+        println!("{:>2}. {}", i + 1, word);
+    }
+    if matches.len() > PICK_RESULTS {
+        println!("... and {} more, search for something longer to narrow it down", matches.len() - PICK_RESULTS);
+    }
+    let choice = input(Some("Word number (type q) > "));
+    if choice.to_lowercase() == "q" {
+        return;
+    }
+    match choice.parse::<usize>() {
+        Ok(number) if number >= 1 && number <= matches.len().min(PICK_RESULTS) => {
+            game.play_with(matches[number - 1].clone());
+            guess_loop(game);
+        }
+        _ => println!("{color_red}Invalid choice.{color_reset}"),
+    }
+}
+
+fn leaderboard(stats: &Stats) {
+    let entries = stats.leaderboard();
+    if entries.is_empty() {
+        println!("No personal bests yet, win a game first!");
+        return;
+    }
+    println!("{color_cyan}Personal bests{color_reset}");
+    for (rank, (word, best)) in entries.iter().enumerate() {
+        println!("{:>3}. {} in {} tries ({} seconds)", rank + 1, word, best.tries, best.seconds);
+    }
+}
+
+// inner width of the `stats --card` box
+const STATS_CARD_WIDTH: usize = 30;
+
+/// The stats as plain text lines: games, win rate, streaks and the tries of the wins.
+fn stats_lines(stats: &Stats) -> Vec<String> {
+    let played = stats.games.len();
+    let won = stats.games.iter().filter(|g| g.won).count();
+    let (current, longest) = stats.streaks();
+    let mut lines = vec![
+        format!("Played {}, won {}%", played, (won * 100).checked_div(played).unwrap_or(0)),
+        format!("Streak {}, best streak {}", current, longest),
+    ];
+    // room for the try number, the bar and the count
+    lines.extend(render_histogram(&stats.tries_distribution(), STATS_CARD_WIDTH - 10));
+    lines
+}
+
+/// `stats` prints the stats, `stats --card` the same in a plain text box to paste into a chat.
+fn show_stats(stats: &Stats, arguments: &str) {
+    if stats.games.is_empty() {
+        println!("No games played yet.");
+        return;
+    }
+    match arguments.trim() {
+        "" => {
+            println!("{color_cyan}Stats{color_reset}");
+            for line in stats_lines(stats) {
+                println!("{}", line);
+            }
+        }
+        "--card" => println!("{}", stats_card(stats)),
+        _ => println!("{color_red}Usage: stats [--card]{color_reset}"),
+    }
+}
+
+/// [`stats_lines`] in a box no wider than [`STATS_CARD_WIDTH`], without colors.
+fn stats_card(stats: &Stats) -> String {
+    let border = format!("+{}+", "-".repeat(STATS_CARD_WIDTH + 2));
+    let mut card = vec![border.clone()];
+    for line in std::iter::once("RUDLE stats".to_string()).chain(stats_lines(stats)) {
+        let line = line.chars().take(STATS_CARD_WIDTH).collect::<String>();
+        card.push(format!("| {:<width$} |", line, width = STATS_CARD_WIDTH));
+    }
+    card.push(border);
+    card.join("\n")
+}
+
+/// Lists every answer `validate_answers` rejects, checked against `--length` or the most common length.
+fn check_dictionary(game: &Game) {
+    let Some(length) = game.word_length.or_else(|| {
+        game.dictionary
+            .lengths()
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(length, _)| length)
+    }) else {
+        println!("{color_red}The dictionary is empty.{color_reset}");
+        return;
+    };
+    let invalid = game.dictionary.validate_answers(length, &game.blocked_guesses);
+    if invalid.is_empty() {
+        println!(
+            "{color_green}All {} words are valid {} letters answers.{color_reset}",
+            game.dictionary.words.len(),
+            length
+        );
+        return;
+    }
+    println!(
+        "{color_yellow}{} of {} words can't be {} letters answers:{color_reset}",
+        invalid.len(),
+        game.dictionary.words.len(),
+        length
+    );
+    for word in invalid {
+        println!("  {:?}", word);
+    }
+}
+
+/// Word starts or ends with how many words have them.
+type AffixCounts = Vec<(String, usize)>;
+
+// how many starts and ends `affixes` lists
+const AFFIXES_SHOWN: usize = 10;
+
+/// The most common starts and ends of the possible answers, to help picking an opener.
+fn affixes(game: &Game) {
+    let (prefixes, suffixes) = game.answers().common_affixes(AFFIXES_SHOWN);
+    if prefixes.is_empty() {
+        println!("{color_red}There are no answers to look at.{color_reset}");
+        return;
+    }
+    let list = |affixes: AffixCounts| {
+        affixes
+            .into_iter()
+            .map(|(affix, count)| format!("{} ({})", affix, count))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    println!("{color_cyan}Most common starts:{color_reset} {}", list(prefixes));
+    println!("{color_cyan}Most common ends:{color_reset} {}", list(suffixes));
+}
+
+/// The colors of a pasted share grid, one row per line with tiles (🟩 🟨 ⬛ or ⬜). Lines without
+/// tiles (like a title) are skipped, the letters of the rows are unknown and left as `_`.
+fn parse_share_grid(text: &str) -> Result<Vec<Vec<Guess>>, String> {
+    let mut rows = Vec::new();
+    for line in text.lines() {
+        let mut row = Vec::new();
+        for c in line.chars().filter(|c| !c.is_whitespace()) {
+            row.push(match c {
+                '🟩' => Guess::Correct('_'),
+                '🟨' => Guess::Missed('_'),
+                '⬛' | '⬜' => Guess::Incorrect('_'),
+                _ if row.is_empty() => break,
+                _ => return Err(format!("{} is not a tile", c)),
+            });
+        }
+        if !row.is_empty() {
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Why `rows` (from [`parse_share_grid`]) can't be a game of `answer` that ended as claimed,
+/// `None` if it can. Every row has to be the colors of some word of `words` against the answer.
+fn share_grid_problem(rows: &[Vec<Guess>], answer: &str, won: bool, words: &[String]) -> Option<String> {
+    let colors = |row: &[Guess]| row.iter().map(std::mem::discriminant).collect::<Vec<_>>();
+    let solved = |row: &[Guess]| row.iter().all(|g| matches!(g, Guess::Correct(_)));
+    let length = answer.chars().count();
+    let possible = words
+        .iter()
+        .filter(|w| w.chars().count() == length)
+        .map(|w| colors(&feedback(answer, w)))
+        .collect::<HashSet<_>>();
+    if rows.is_empty() {
+        return Some("there are no rows".to_string());
+    }
+    for (i, row) in rows.iter().enumerate() {
+        if row.len() != length {
+            return Some(format!("row {} has {} tiles, the answer has {} letters", i + 1, row.len(), length));
+        }
+        if !possible.contains(&colors(row)) {
+            return Some(format!("no word gives the colors of row {}", i + 1));
+        }
+        if solved(row) && i + 1 < rows.len() {
+            return Some(format!("row {} is already solved but the game goes on", i + 1));
+        }
+    }
+    match (won, solved(rows.last().unwrap())) {
+        (true, false) => Some("the last row isn't all green, so it wasn't a win".to_string()),
+        (false, true) => Some("the last row is all green, so it was a win".to_string()),
+        _ => None,
+    }
+}
+
+/// `verify-share <answer> [win|loss]`: reads a pasted share grid and checks it could be real.
+fn verify_share(game: &Game, arguments: &str) {
+    let arguments = arguments.split_whitespace().collect::<Vec<&str>>();
+    let (answer, won) = match arguments[..] {
+        [answer] | [answer, "win"] => (answer.to_lowercase(), true),
+        [answer, "loss"] => (answer.to_lowercase(), false),
+        _ => {
+            println!("{color_red}Usage: verify-share <answer> [win|loss]{color_reset}");
+            return;
+        }
+    };
+    println!("Paste the grid, then an empty line:");
+    let mut grid = Vec::new();
+    loop {
+        let line = input(None);
+        if line.trim().is_empty() {
+            break;
+        }
+        grid.push(line);
+    }
+    let rows = match parse_share_grid(&grid.join("\n")) {
+        Ok(rows) => rows,
+        Err(e) => {
+            println!("{color_red}ERROR: {}{color_reset}", e);
+            return;
+        }
+    };
+    match share_grid_problem(&rows, &answer, won, &game.dictionary.words) {
+        None => println!("{color_green}That grid is possible for {}.{color_reset}", answer),
+        Some(problem) => println!("{color_red}That grid can't be real: {}.{color_reset}", problem),
+    }
+}
+
+/// Lists the words only one of two dictionary files (text or JSON, in any mix) has, for
+/// `diff-dict <a> <b>`.
+fn diff_dict(arguments: &str) {
+    let paths = arguments.split_whitespace().collect::<Vec<&str>>();
+    let [a, b] = paths[..] else {
+        println!("{color_red}Usage: diff-dict <a> <b>{color_reset}");
+        return;
+    };
+    let mut dictionaries = Vec::new();
+    for path in [a, b] {
+        match read_word_list(&PathBuf::from(path)) {
+            Ok(words) => dictionaries.push(words),
+            Err(e) => {
+                println!("{color_red}Failed to load {}.{color_reset} ({})", path, e);
+                return;
+            }
+        }
+    }
+    let (only_a, only_b, both) = compare_word_lists(&dictionaries[0], &dictionaries[1]);
+    for (path, words) in [(a, only_a), (b, only_b)] {
+        println!("{color_cyan}Only in {} ({}):{color_reset}", path, words.len());
+        for word in words {
+            println!("  {}", word);
+        }
+    }
+    println!("{} words are in both.", both.len());
+}
+
+/// The words only in `a`, only in `b` and in both, each sorted and without duplicates.
+fn compare_word_lists(a: &[String], b: &[String]) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let a = a.iter().cloned().collect::<HashSet<String>>();
+    let b = b.iter().cloned().collect::<HashSet<String>>();
+    let sorted = |words: Vec<&String>| {
+        let mut words = words.into_iter().cloned().collect::<Vec<String>>();
+        words.sort();
+        words
+    };
+    (
+        sorted(a.difference(&b).collect()),
+        sorted(b.difference(&a).collect()),
+        sorted(a.intersection(&b).collect()),
+    )
+}
+
+/// Lets the solver play `simulate [games]` random answers and shows how many tries they took.
+fn simulate(game: &Game, arguments: &str) {
+    let games = match arguments.trim() {
+        "" => 100,
+        games => match games.parse::<usize>() {
+            Ok(games) if games > 0 => games,
+            Ok(_) => {
+                println!("{color_red}At least one game is required.{color_reset}");
+                return;
+            }
+            Err(e) => {
+                println!("{color_red}Invalid number of games.{color_reset} ({})", e);
+                return;
+            }
+        },
+    };
+    let answers = game.answers();
+    if answers.words.is_empty() {
+        println!("{color_red}There are no answers to simulate.{color_reset}");
+        return;
+    }
+    // index 0 holds the losses, the rest the wins by tries
+    let mut distribution = vec![0; game.max_tries as usize + 1];
+    // the first guess is always the same, no need to work it out every game
+    let Some(opening) = suggest(&answers.words.iter().collect::<Vec<&String>>()) else {
+        return;
+    };
+    for i in 0..games {
+        let answer = answers.random();
+        distribution[solve(&answers, &opening, &answer, game.max_tries).unwrap_or(0) as usize] += 1;
+        if games >= 100 && (i + 1) % (games / 10) == 0 {
+            print!("\rSimulated {}/{} games", i + 1, games);
+            std::io::stdout().flush().ok();
+        }
+    }
+    if games >= 100 {
+        println!();
+    }
+    let mut bars = (1..distribution.len())
+        .map(|tries| (tries.to_string(), distribution[tries]))
+        .collect::<Vec<(String, usize)>>();
+    bars.push(("X".to_string(), distribution[0]));
+    for line in render_histogram(&bars, 30) {
+        println!("{}", line);
+    }
+    println!(
+        "Won {}/{} games ({:.1}%)",
+        games - distribution[0],
+        games,
+        (games - distribution[0]) as f64 * 100.0 / games as f64
+    );
+}
+
+// replay <file> [delay in milliseconds]
+fn replay(arguments: &str, theme: &Theme) {
+    let mut arguments = arguments.split_whitespace();
+    let Some(path) = arguments.next() else {
+        println!("{color_red}A saved game file is required.{color_reset}");
+        return;
+    };
+    let delay = match arguments.next().map(|d| d.parse::<u64>()) {
+        Some(Ok(delay)) => delay,
+        Some(Err(e)) => {
+            println!("{color_red}Invalid delay.{color_reset} ({})", e);
+            return;
+        }
+        None => 800,
+    };
+    let saved = match SavedGame::load(&PathBuf::from(path)) {
+        Ok(saved) => saved,
+        Err(e) => {
+            println!("{color_red}Failed to load the saved game.{color_reset} ({})", e);
+            return;
+        }
+    };
+    let mut game = Game::new(Dictionary::new(), false);
+    game.theme = *theme;
+    game.word = saved.word.clone();
+    for row in saved.guesses {
+        game.guesses.push(row);
+        clear_screen();
+        println!("{}", render_board(&game, false));
+        std::thread::sleep(std::time::Duration::from_millis(delay));
+    }
+    println!();
+    println!("{color_cyan}The word was {}{color_reset}", saved.word);
+}
+
+fn guess_loop(game: &mut Game) {
+    // the last guess that got rejected, shown in the next prompt so it can be fixed
+    let mut rejected: Option<String> = None;
+    clear_screen();
+    if game.easy {
+        println!("{}", render_revealed(&game.revealed_letters(), &game.theme));
+        println!();
+    }
+    if let Some(size) = game.pool_size.filter(|_| game.show_pool) {
+        println!("Drawing from {} possible answers", size);
+        println!();
+    }
+    if game.entropy_gauge && game.word.chars().count() <= 10 {
+        let answers = game.answers().with_length(game.word.chars().count());
+        let words = answers.words.iter().collect::<Vec<&String>>();
+        game.pattern_table = Some(PatternTable::load_or_build(game, &words, paths::cache_dir()));
+    }
+    if game.letter_odds {
+        let answers = game.answers();
+        let length = game.word.chars().count();
+        let total = answers.with_length(length).words.len().max(1);
+        let picks = answers
+            .position_frequencies(length)
+            .iter()
+            .enumerate()
+            .filter_map(|(i, letters)| {
+                letters.first().map(|(letter, count)| {
+                    format!("{}: {} ({}%)", i + 1, letter, count * 100 / total)
+                })
+            })
+            .collect::<Vec<String>>();
+        println!("Most likely letters: {}", picks.join(", "));
+        println!();
+    }
+    loop {
+        if game.config.auto_save {
+            if let Err(e) = AutoSave::save(game) {
+                println!("{color_red}Failed to save the game in progress.{color_reset} ({})", e);
+            }
+        }
+        if game.reveal {
+            println!("{color_cyan}{} (Word is {}) (Tries: {}/{} Tries{}){}{color_reset}", game.config.ui.banner, game.word, game.tries, game.max_tries, game.mode_label(), game.timer_label() + &game.budget_label());
+        } else {
+            println!("{color_cyan}{} (Word is {} characters long) (Tries: {}/{} Tries{}){}{color_reset}", game.config.ui.banner, game.word.chars().count(), game.tries, game.max_tries, game.mode_label(), game.timer_label() + &game.budget_label());
+        }
+        let mut prompt = if game.team {
+            // the players take turns, the board is only drawn once both guessed
+            format!("Player {} {}", game.player(), game.config.ui.guess_prompt)
+        } else {
+            game.config.ui.guess_prompt.clone()
+        };
+        if let Some(typed) = rejected.take().filter(|_| !game.hidden_input) {
+            prompt = format!("(you typed {}) {}", typed, prompt);
+        }
+        let input = if game.hidden_input { input_hidden(&prompt) } else { input(Some(&prompt)) };
+        // the time spent typing counts, so this is checked once the line is in
+        if game.time_left_now().is_some_and(|left| left.is_zero()) {
+            game.playing = false;
+            show_board(game, &game.shown_rows(&game.guesses));
+            show_end_banner(&game.config.ui, false);
+            println!("{color_yellow}Time is up, game over.{color_reset}");
+            println!("{color_red}The word was {}{color_reset}", game.word);
+            finish(game, false);
+            break;
+        }
+        if input.to_lowercase() == "/board" {
+            if game.blind {
+                println!("{color_red}ERROR: the board stays hidden until the game is over in blind mode{color_reset}");
+                continue;
+            }
+            show_board(game, &game.shown_rows(&game.guesses));
+            println!();
+            continue;
+        }
+        if input.to_lowercase() == "/hint" {
+            match game.hint() {
+                Ok((position, letter)) => println!(
+                    "{color_green}Hint: letter {} is {}{color_reset} ({} hints left)",
+                    position + 1,
+                    letter,
+                    game.max_hints - game.hints_used
+                ),
+                Err(e) => println!("{color_red}ERROR: {}{color_reset}", e),
+            }
+            continue;
+        }
+        if input.to_lowercase() == "/mulligan" {
+            if !game.mulligan {
+                println!("{color_red}ERROR: run with --mulligan to take back a guess{color_reset}");
+                continue;
+            }
+            match game.mulligan() {
+                Ok(()) => {
+                    show_turn(game);
+                    println!("{color_yellow}The last guess was taken back, that was your only mulligan.{color_reset}");
+                }
+                Err(e) => println!("{color_red}ERROR: {}{color_reset}", e),
+            }
+            continue;
+        }
+        if input.to_lowercase() == "/anagram" {
+            match game.anagram_hint() {
+                Ok(letters) => println!(
+                    "{color_green}The answer is made of {}{color_reset} ({} hints left)",
+                    letters,
+                    game.max_hints - game.hints_used
+                ),
+                Err(e) => println!("{color_red}ERROR: {}{color_reset}", e),
+            }
+            continue;
+        }
+        if input.to_lowercase() == "/suggest" {
+            if game.fog {
+                println!("{color_red}ERROR: suggestions would give away the yellows that fog hides{color_reset}");
+                continue;
+            }
+            let possible = game.possible_answers_iter().map(str::to_string).collect::<Vec<String>>();
+            let candidates = possible.iter().collect::<Vec<&String>>();
+            match suggest(&candidates) {
+                Some(word) => println!("{color_green}Try {}{color_reset} ({} possible answers left)", word, candidates.len()),
+                None => println!("{color_red}ERROR: no word fits what the board says{color_reset}"),
+            }
+            continue;
+        }
+        if input.to_lowercase() == "/quit" || input.to_lowercase() == "/menu" {
+            if self::input(Some("Abandon this game? (y/N) > ")).to_lowercase() == "y" {
+                forfeit(game);
+                break;
+            }
+            continue;
+        }
+        if let Some(path) = input.strip_prefix("/add-answer ") {
+            if game.reveal {
+                add_answer(game, PathBuf::from(path.trim()));
+            } else {
+                println!("{color_red}ERROR: the answer can only be added mid-game with --reveal, try again after the game{color_reset}");
+            }
+            continue;
+        }
+        let input = match sanitize_guess(&input, game.word.chars().count(), game.length_policy) {
+            Some((guess, true)) => {
+                println!("{color_yellow}Guessing {} instead of {}{color_reset}", guess, input);
+                guess
+            }
+            _ => input,
+        };
+        let typed = input.clone();
+        let guesses = game.submit_guess(input);
+        match guesses {
+            Ok(_) => {
+                let penalized = game.risky && !game.is_known(&typed);
+                if game.bell && found_new_green(&game.guesses) {
+                    notify(Notification::Progress);
+                }
+                show_turn(game);
+                if penalized {
+                    println!("{color_yellow}{} isn't in the dictionary, that cost an extra try.{color_reset}", typed);
+                }
+                if let Some(table) = game.pattern_table.as_ref().filter(|_| game.entropy_gauge) {
+                    let before = table.candidates(&game.guesses[..game.guesses.len() - 1]);
+                    let bits = table.entropy(&typed, &before);
+                    let best = (before.len() as f64).log2();
+                    println!(
+                        "That guess told apart {:.2} bits of {:.2} ({} of {} possible answers left)",
+                        bits,
+                        best,
+                        table.candidates(&game.guesses).len(),
+                        before.len()
+                    );
+                }
+                if let Some((position, letter)) = game.mercy_hint {
+                    println!("{color_green}Having a hard time? Letter {} is {}{color_reset}", position + 1, letter);
+                }
+            }
+            Err((e, typed_back)) => match e {
+                Errors::MaximumTries(word, guesses) => {
+                    show_board(game, &game.shown_rows(&guesses));
+                    show_end_banner(&game.config.ui, false);
+                    println!("{color_yellow}Maximum tries reached, exiting...{color_reset}");
+                    println!("{color_red}The word was {}{color_reset}", word);
+                    if let Some((i, greens)) = closest_guess(&guesses) {
+                        println!(
+                            "Your closest guess ({}) had {}/{} letters in the right place.",
+                            guesses[i].iter().map(|g| g.get_letter()).collect::<String>(),
+                            greens,
+                            word.chars().count()
+                        );
+                    }
+                    if game.config.show_nearest_guess {
+                        if let Some((guess, distance)) = nearest_guess(&word, &guesses) {
+                            println!("Your guess '{}' was closest, {} letter edits away.", guess, distance);
+                        }
+                    }
+                    // no pacing when the output goes to a file or another program
+                    if game.config.loss_reveal_delay_ms > 0 && std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+                        std::thread::sleep(std::time::Duration::from_millis(game.config.loss_reveal_delay_ms));
+                    }
+                    let scored = game.scored_rows(&guesses);
+                    println!("{color_green}Your accuracy is {}%{color_reset}", calculate_guess_accuracy(scored.clone(), game.hints_used as usize, &game.config.scoring) * 100.0);
+                    if game.verbose {
+                        show_accuracy_breakdown(&scored, game.hints_used as usize, &game.config.scoring);
+                    }
+                    finish(game, false);
+                    break;
+                },
+                Errors::GameEndedWin(tries, max_tries, guesses) => {
+                    if game.bell {
+                        notify(Notification::Win);
+                    }
+                    clear_screen();
+                    show_board(game, &game.shown_rows(&guesses));
+                    println!();
+                    show_end_banner(&game.config.ui, true);
+                    println!("{color_green}You win!{color_reset}");
+                    println!("{bg_black}{color_bright_white} Took {}/{} tries.{color_reset}{bg_reset}", tries - 1, max_tries);
+                    if game.arcade {
+                        let seconds = game.started.map(|s| s.elapsed().as_secs()).unwrap_or(0);
+                        let score = arcade_score(seconds, guesses.len(), &game.config.scoring);
+                        println!("{bg_yellow}{color_black} ARCADE SCORE: {} {color_reset}{bg_reset}", score);
+                    }
+                    let scored = game.scored_rows(&guesses);
+                    println!("{color_green}Your accuracy is {}%{color_reset}", calculate_guess_accuracy(scored.clone(), game.hints_used as usize, &game.config.scoring) * 100.0);
+                    if game.verbose {
+                        show_accuracy_breakdown(&scored, game.hints_used as usize, &game.config.scoring);
+                    }
+                    finish(game, true);
+                    // no retries on the daily and hourly puzzles, they are the same for everyone
+                    if game.daily_number.is_none()
+                        && game.hourly_number.is_none()
+                        && crate::input(Some("Retry for a faster time? (y/N) > ")).to_lowercase() == "y"
+                    {
+                        game.restart_same_word();
+                        clear_screen();
+                        continue;
+                    }
+                    break;
+                }
+                _ => {
+                    println!("{color_red}ERROR: {}{color_reset}", e);
+                    if let Some(record) = game.hardcore_loss(&e) {
+                        show_end_banner(&game.config.ui, false);
+                        println!("{color_yellow}Hardcore mode doesn't forgive invalid guesses, game over.{color_reset}");
+                        println!("{color_red}The word was {}{color_reset}", game.word);
+                        show_summary(game);
+                        save_result(game, &record);
+                        break;
+                    }
+                    rejected = typed_back;
+                }
+            },
+        }
+    }
+    if game.config.auto_save && !game.playing {
+        AutoSave::clear();
+    }
+}
+
+/// Colors `guess` against `answer`: greens first, then yellows as long as the answer has copies
+/// of the letter left that weren't matched already. Letters are compared through `fold`.
+fn feedback_with(answer: &[char], guess: &[char], fold: impl Fn(char) -> char) -> Vec<Guess> {
+    let mut guesses = vec![Guess::Incorrect('_'); answer.len()];
+    let mut remaining: HashMap<char, i64> = HashMap::new();
+    for (i, letter) in guess.iter().enumerate() {
+        if fold(answer[i]) == fold(*letter) {
+            guesses[i] = Guess::Correct(answer[i]); // keep the accent of the answer
+        } else {
+            *remaining.entry(fold(answer[i])).or_insert(0) += 1;
+        }
+    }
+    for (i, letter) in guess.iter().enumerate() {
+        if guesses[i] == Guess::Incorrect('_') { // Only check remaining letters
+            let count = remaining.entry(fold(*letter)).or_insert(0);
+            if *count > 0 {
+                guesses[i] = Guess::Missed(*letter);
+                *count -= 1;
+            } else {
+                guesses[i] = Guess::Incorrect(*letter);
+            }
+        }
+    }
+    guesses
+}
+
+/// Greens like [`feedback_with`], but any other letter that is somewhere in the answer is yellow no
+/// matter how many copies of it were already colored.
+fn feedback_any_occurrence(answer: &[char], guess: &[char], fold: impl Fn(char) -> char) -> Vec<Guess> {
+    guess
+        .iter()
+        .enumerate()
+        .map(|(i, letter)| {
+            if fold(answer[i]) == fold(*letter) {
+                Guess::Correct(answer[i])
+            } else if answer.iter().any(|c| fold(*c) == fold(*letter)) {
+                Guess::Missed(*letter)
+            } else {
+                Guess::Incorrect(*letter)
+            }
+        })
+        .collect()
+}
+
+/// The colors `guess` gets against `answer` in `coloring_mode`, with accents folded if `normalize_accents`.
+fn score_with(coloring_mode: ColoringMode, normalize_accents: bool, answer: &str, guess: &str) -> Vec<Guess> {
+    let fold = |c: char| if normalize_accents { strip_accents(c) } else { c };
+    let answer = answer.chars().collect::<Vec<char>>();
+    let guess = guess.chars().collect::<Vec<char>>();
+    match coloring_mode {
+        ColoringMode::Standard => feedback_with(&answer, &guess, fold),
+        ColoringMode::AnyOccurrence => feedback_any_occurrence(&answer, &guess, fold),
+    }
+}
+
+fn feedback(answer: &str, guess: &str) -> Vec<Guess> {
+    feedback_with(
+        &answer.chars().collect::<Vec<char>>(),
+        &guess.chars().collect::<Vec<char>>(),
+        |c| c,
+    )
+}
+
+/// Whether `word` could be the answer given the colored rows seen so far.
+fn consistent(word: &str, rows: &[Vec<Guess>]) -> bool {
+    rows.iter().all(|row| {
+        let guess = row.iter().map(|g| g.get_letter()).collect::<String>();
+        word.chars().count() == row.len() && feedback(word, &guess) == *row
+    })
+}
+
+/// The candidate that covers the most common letters among the candidates: each distinct letter of
+/// a word scores the number of candidates having it. Equal scores are broken alphabetically, so the
+/// same candidates always give the same suggestion.
+fn suggest(candidates: &[&String]) -> Option<String> {
+    rank_by_letters(candidates).first().map(|w| w.to_string())
+}
+
+/// Every candidate ordered the way [`suggest`] picks them, best first.
+fn rank_by_letters<'a>(candidates: &[&'a String]) -> Vec<&'a String> {
+    let mut frequencies: HashMap<char, usize> = HashMap::new();
+    for word in candidates {
+        for letter in word.chars().collect::<HashSet<char>>() {
+            *frequencies.entry(letter).or_insert(0) += 1;
+        }
+    }
+    let score = |word: &str| {
+        word.chars()
+            .collect::<HashSet<char>>()
+            .iter()
+            .map(|c| frequencies[c])
+            .sum::<usize>()
+    };
+    let mut ranked = candidates.iter().map(|w| (score(w), *w)).collect::<Vec<(usize, &String)>>();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    ranked.into_iter().map(|(_, w)| w).collect()
+}
+
+/// How much `guess` is expected to tell apart `candidates`, in bits: the entropy of the colors it
+/// would get against each of them, colored by `score(answer, guess)`.
+fn pattern_entropy(guess: &str, candidates: &[&String], score: &impl Fn(&str, &str) -> Vec<Guess>) -> f64 {
+    let mut patterns: HashMap<Vec<Guess>, usize> = HashMap::new();
+    for candidate in candidates {
+        *patterns.entry(score(candidate, guess)).or_insert(0) += 1;
+    }
+    let total = candidates.len() as f64;
+    patterns
+        .values()
+        .map(|count| {
+            let p = *count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+// most patterns a PatternTable holds (2 bytes each), past it fewer guesses get a column
+const PATTERN_TABLE_LIMIT: usize = 2_000_000;
+
+/// The colors of a row as one number, each tile is a base 3 digit. Only rows up to 10 letters fit.
+fn pattern_code(row: &[Guess]) -> u16 {
+    row.iter().fold(0, |code, g| {
+        code * 3
+            + match g {
+                Guess::Incorrect(_) => 0,
+                Guess::Missed(_) => 1,
+                Guess::Correct(_) => 2,
+            }
+    })
+}
+
+/// The colors every guess of `guesses` gets against every answer, computed once and cached on
+/// disk, so the entropy of a guess doesn't have to score it against every answer again.
+// start of a cached pattern table, followed by its key and the numbers of guesses and answers
+const PATTERN_TABLE_MAGIC: &[u8; 4] = b"RPT1";
+const PATTERN_TABLE_HEADER: usize = 4 + 8 + 4 + 4;
+
+#[derive(Debug, Clone)]
+struct PatternTable {
+    answers: Vec<String>,
+    guesses: HashMap<String, usize>,
+    // the code of guess `g` against answer `a` is at `g * answers.len() + a`
+    patterns: Vec<u16>,
+    // scored the way the game scores, so codes match the rows on the board
+    coloring_mode: ColoringMode,
+    normalize_accents: bool,
+}
+
+impl PatternTable {
+    /// The table of `answers` against themselves, or against the best ones by letters when that is
+    /// more than [`PATTERN_TABLE_LIMIT`], scored like [`Game::score`]. It's read from `cache_dir`
+    /// if it was built there before.
+    fn load_or_build(game: &Game, answers: &[&String], cache_dir: Option<PathBuf>) -> Self {
+        let answers = answers.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        let shortlist = rank_by_letters(&answers.iter().collect::<Vec<&String>>())
+            .into_iter()
+            .take(PATTERN_TABLE_LIMIT / answers.len().max(1))
+            .cloned()
+            .collect::<Vec<String>>();
+        let scoring = format!("{:?} {}", game.coloring_mode, game.normalize_accents);
+        // FNV-1a like the seed phrases, it has to come out the same in every build
+        let key = phrase_seed(&format!("{}\n{}\n{}", scoring, answers.join(" "), shortlist.join(" ")));
+        let mut header = PATTERN_TABLE_MAGIC.to_vec();
+        header.extend(key.to_le_bytes());
+        header.extend((shortlist.len() as u32).to_le_bytes());
+        header.extend((answers.len() as u32).to_le_bytes());
+        let guesses = shortlist.iter().enumerate().map(|(i, w)| (w.clone(), i)).collect();
+        let path = cache_dir.map(|dir| dir.join(format!("patterns-{:016x}.bin", key)));
+        let expected = shortlist.len() * answers.len();
+        let cached = path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .filter(|bytes| bytes.len() == PATTERN_TABLE_HEADER + expected * 2 && bytes.starts_with(&header))
+            .map(|bytes| {
+                bytes[PATTERN_TABLE_HEADER..].chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect()
+            });
+        let patterns = match cached {
+            Some(patterns) => patterns,
+            None => {
+                let patterns = shortlist
+                    .iter()
+                    .flat_map(|guess| answers.iter().map(move |answer| pattern_code(&game.score(answer, guess))))
+                    .collect::<Vec<u16>>();
+                if let Some(path) = &path {
+                    header.extend(patterns.iter().flat_map(|p| p.to_le_bytes()));
+                    // only a cache, it is built again next time if it can't be written
+                    write_atomic(path, &header).ok();
+                }
+                patterns
+            }
+        };
+        Self {
+            answers,
+            guesses,
+            patterns,
+            coloring_mode: game.coloring_mode,
+            normalize_accents: game.normalize_accents,
+        }
+    }
+
+    /// The code of `guess` against answer number `answer`, scored on the spot if the guess has no column.
+    fn pattern(&self, guess: &str, answer: usize) -> u16 {
+        match self.guesses.get(guess) {
+            Some(g) => self.patterns[g * self.answers.len() + answer],
+            None => pattern_code(&score_with(
+                self.coloring_mode,
+                self.normalize_accents,
+                &self.answers[answer],
+                guess,
+            )),
+        }
+    }
+
+    /// Numbers of the answers that give the colors of every row.
+    fn candidates(&self, rows: &[Vec<Guess>]) -> Vec<usize> {
+        let rows = rows
+            .iter()
+            .map(|row| (row.iter().map(|g| g.get_letter()).collect::<String>(), pattern_code(row)))
+            .collect::<Vec<(String, u16)>>();
+        (0..self.answers.len())
+            .filter(|a| rows.iter().all(|(guess, code)| self.pattern(guess, *a) == *code))
+            .collect()
+    }
+
+    /// [`pattern_entropy`] of `guess` among the answers numbered `candidates`.
+    fn entropy(&self, guess: &str, candidates: &[usize]) -> f64 {
+        let mut patterns: HashMap<u16, usize> = HashMap::new();
+        for a in candidates {
+            *patterns.entry(self.pattern(guess, *a)).or_insert(0) += 1;
+        }
+        let total = candidates.len() as f64;
+        patterns
+            .values()
+            .map(|count| {
+                let p = *count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+// how many of the best letter scores get their entropy compared, checking every word is too slow
+const OPTIMAL_SHORTLIST: usize = 30;
+// a guess is optimal if its entropy is within the best few of the shortlist
+const OPTIMAL_TOP: usize = 5;
+
+/// Whether `guess` was one of the most informative guesses while `candidates` were still possible,
+/// with the colors coming from `score(answer, guess)`.
+fn was_optimal(guess: &str, candidates: &[&String], score: &impl Fn(&str, &str) -> Vec<Guess>) -> bool {
+    if candidates.len() <= 2 {
+        return candidates.iter().any(|c| *c == guess);
+    }
+    let mut entropies = rank_by_letters(candidates)
+        .into_iter()
+        .take(OPTIMAL_SHORTLIST)
+        .filter(|w| *w != guess)
+        .map(|w| pattern_entropy(w, candidates, score))
+        .collect::<Vec<f64>>();
+    entropies.sort_by(|a, b| b.total_cmp(a));
+    match entropies.get(OPTIMAL_TOP - 1) {
+        Some(threshold) => pattern_entropy(guess, candidates, score) >= *threshold - 1e-9,
+        None => true,
+    }
+}
+
+/// Plays `answer` starting with `opening` and then with [`suggest`] picking every guess, returns
+/// the tries it took or `None` if it ran out of tries.
+fn solve(answers: &Dictionary, opening: &str, answer: &str, max_tries: u64) -> Option<u64> {
+    let mut candidates = answers.words.iter().collect::<Vec<&String>>();
+    let mut guess = opening.to_string();
+    for tries in 1..=max_tries {
+        if guess == answer {
+            return Some(tries);
+        }
+        let row = feedback(answer, &guess);
+        candidates.retain(|w| consistent(w, std::slice::from_ref(&row)));
+        guess = suggest(&candidates)?;
+    }
+    None
+}
+
+/// One bar per label, scaled so the longest bar is `width` characters.
+fn render_histogram(bars: &[(String, usize)], width: usize) -> Vec<String> {
+    let most = bars.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+    let label_width = bars.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    bars.iter()
+        .map(|(label, count)| {
+            format!(
+                "{:>label_width$} | {} {}",
+                label,
+                "#".repeat(count * width / most),
+                count
+            )
+        })
+        .collect()
+}
+
+/// Positions of the guesses that didn't rule out any answer that was still possible, out of the
+/// dictionary words as long as the answer and the answer itself, which a fixed word may not be one of.
+fn wasted_guesses(game: &Game) -> Vec<usize> {
+    let mut words = game.dictionary.with_length(game.word.chars().count()).words;
+    if !words.contains(&game.word) {
+        words.push(game.word.clone());
+    }
+    let mut remaining = game.candidates(&words, &[]).len();
+    let mut wasted = Vec::new();
+    for i in 0..game.guesses.len() {
+        let after = game.candidates(&words, &game.guesses[..=i]).len();
+        let won = game.guesses[i].iter().all(|g| matches!(g, Guess::Correct(_)));
+        if after == remaining && !won {
+            wasted.push(i);
+        }
+        remaining = after;
+    }
+    wasted
+}
+
+/// Letters each position can still hold: greens pin a position, yellows and grays rule a letter out
+/// where they were played, and a gray letter with no green or yellow copy in its row is ruled out
+/// everywhere it isn't green already.
+fn letter_bank(length: usize, guesses: &[Vec<Guess>], alphabet: &[char]) -> Vec<Vec<char>> {
+    let mut bank = vec![alphabet.iter().copied().collect::<HashSet<char>>(); length];
+    for row in guesses {
+        for (i, guess) in row.iter().enumerate().take(length) {
+            match guess {
+                Guess::Correct(letter) => bank[i] = HashSet::from([*letter]),
+                Guess::Missed(letter) => {
+                    bank[i].remove(letter);
+                }
+                Guess::Incorrect(letter) => {
+                    bank[i].remove(letter);
+                    let elsewhere = row.iter().any(|g| {
+                        g.get_letter() == *letter && !matches!(g, Guess::Incorrect(_))
+                    });
+                    if !elsewhere {
+                        for letters in bank.iter_mut() {
+                            if letters.len() > 1 {
+                                letters.remove(letter);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    bank.into_iter()
+        .map(|letters| {
+            let mut letters = letters.into_iter().collect::<Vec<char>>();
+            letters.sort();
+            letters
+        })
+        .collect()
+}
+
+/// How many letters have to be added, removed or replaced to turn `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(ca != *cb);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The guess with the fewest edits to `answer` (the earliest on a tie) and that distance.
+fn nearest_guess(answer: &str, guesses: &[Vec<Guess>]) -> Option<(String, usize)> {
+    guesses
+        .iter()
+        .map(|row| row.iter().map(|g| g.get_letter()).collect::<String>())
+        .map(|guess| {
+            let distance = levenshtein(&guess, answer);
+            (guess, distance)
+        })
+        .min_by_key(|(_, distance)| *distance)
+}
+
+/// The row with the most green letters (then yellow letters, then the earliest) and its green count.
+fn closest_guess(guesses: &[Vec<Guess>]) -> Option<(usize, usize)> {
+    let count = |row: &[Guess], status: fn(&Guess) -> bool| row.iter().filter(|g| status(g)).count();
+    guesses
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            (
+                i,
+                count(row, |g| matches!(g, Guess::Correct(_))),
+                count(row, |g| matches!(g, Guess::Missed(_))),
+            )
+        })
+        .max_by(|a, b| (a.1, a.2, b.0).cmp(&(b.1, b.2, a.0)))
+        .map(|(i, greens, _)| (i, greens))
+}
+
+/// Letters of `alphabet` that don't appear in any guess, sorted.
+fn unused_letters(guesses: &[Vec<Guess>], alphabet: &str) -> Vec<char> {
+    let used = guesses
+        .iter()
+        .flatten()
+        .map(|g| g.get_letter())
+        .collect::<HashSet<char>>();
+    let mut unused = alphabet
+        .chars()
+        .filter(|c| !used.contains(c))
+        .collect::<Vec<char>>();
+    unused.sort();
+    unused.dedup();
+    unused
+}
+
+/// Letters known to be in the answer that still need a place, a letter twice when two copies are
+/// known. A row shows as many copies as it has greens and yellows of that letter, the most any row
+/// showed is what the answer has at least, and every position a green pinned it to is taken off.
+fn letter_tray(guesses: &[Vec<Guess>]) -> Vec<char> {
+    let mut known = HashMap::<char, usize>::new();
+    let mut placed = HashMap::<char, HashSet<usize>>::new();
+    for row in guesses {
+        let mut copies = HashMap::<char, usize>::new();
+        for (i, guess) in row.iter().enumerate() {
+            match guess {
+                Guess::Correct(letter) => {
+                    *copies.entry(*letter).or_default() += 1;
+                    placed.entry(*letter).or_default().insert(i);
+                }
+                Guess::Missed(letter) => *copies.entry(*letter).or_default() += 1,
+                Guess::Incorrect(_) => {}
+            }
+        }
+        for (letter, count) in copies {
+            let most = known.entry(letter).or_default();
+            *most = (*most).max(count);
+        }
+    }
+    let mut tray = known
+        .into_iter()
+        .flat_map(|(letter, count)| {
+            let left = count.saturating_sub(placed.get(&letter).map_or(0, |p| p.len()));
+            std::iter::repeat_n(letter, left)
+        })
+        .collect::<Vec<char>>();
+    tray.sort();
+    tray
+}
+
+/// `alphabet` as one line of capitals, the letters some guess used dimmed and the rest bright.
+fn alphabet_strip(guesses: &[Vec<Guess>], alphabet: &str) -> String {
+    let used = guesses
+        .iter()
+        .flatten()
+        .map(|g| g.get_letter())
+        .collect::<HashSet<char>>();
+    alphabet
+        .chars()
+        .map(|c| {
+            let color = if used.contains(&c) { color_bright_black } else { color_bright_white };
+            format!("{}{}{color_reset}", color, c.to_uppercase())
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn show_end_banner(ui: &UiConfig, won: bool) {
+    if let Some(banner) = banners::end_banner(ui, won) {
+        let color = if won { color_green } else { color_yellow };
+        println!("{}{}{color_reset}", color, banner.trim_start_matches('\n').trim_end());
+    }
+}
+
+/// Fits `input` to `length` letters following `policy`. Returns the guess and whether it was
+/// changed, or `None` when the policy leaves it alone (the length check then rejects it).
+fn sanitize_guess(input: &str, length: usize, policy: LengthPolicy) -> Option<(String, bool)> {
+    let letters = input.chars().collect::<Vec<char>>();
+    if letters.len() == length {
+        return Some((input.to_string(), false));
+    }
+    match policy {
+        LengthPolicy::Reject => None,
+        LengthPolicy::Truncate | LengthPolicy::Pad if letters.len() > length => {
+            Some((letters[..length].iter().collect(), true))
+        }
+        LengthPolicy::Truncate => None,
+        LengthPolicy::Pad => {
+            let last = *letters.last()?;
+            let mut padded = letters;
+            padded.resize(length, last);
+            Some((padded.into_iter().collect(), true))
+        }
+    }
+}
+
+/// The colors of a row as counts, like "2 correct, 1 present".
+fn summarize_row(row: &[Guess]) -> String {
+    let correct = row.iter().filter(|g| matches!(g, Guess::Correct(_))).count();
+    let present = row.iter().filter(|g| matches!(g, Guess::Missed(_))).count();
+    format!("{} correct, {} present", correct, present)
+}
+
+/// Whether the latest row has a green at a position none of the earlier rows had.
+fn found_new_green(guesses: &[Vec<Guess>]) -> bool {
+    let Some((last, earlier)) = guesses.split_last() else {
+        return false;
+    };
+    last.iter().enumerate().any(|(i, g)| {
+        matches!(g, Guess::Correct(_))
+            && !earlier.iter().any(|row| matches!(row.get(i), Some(Guess::Correct(_))))
+    })
+}
+
+enum Notification {
+    // a new green letter
+    Progress,
+    Win,
+}
+
+/// Rings the terminal bell (`--bell`): once for progress, three times for a win. Nothing is sent
+/// when the output isn't a terminal, so piped or recorded output stays clean.
+fn notify(kind: Notification) {
+    if !std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+        return;
+    }
+    let bells = match kind {
+        Notification::Progress => 1,
+        Notification::Win => 3,
+    };
+    for i in 0..bells {
+        if i > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(150));
+        }
+        print!("\x07");
+        std::io::stdout().flush().ok();
+    }
+}
+
+/// Whether each guess was among the most informative ones while the dictionary words of the
+/// answer's length that fit the rows before it were possible, scored like the game.
+fn optimal_guesses(game: &Game) -> Vec<bool> {
+    let length = game.word.chars().count();
+    let words = game.dictionary.with_length(length).words;
+    game.guesses
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let guess = row.iter().map(|g| g.get_letter()).collect::<String>();
+            let score = |answer: &str, guess: &str| game.score(answer, guess);
+            was_optimal(&guess, &game.candidates(&words, &game.guesses[..i]), &score)
+        })
+        .collect()
+}
+
+/// The board with a ✓ after every guess that was among the most informative ones at the time.
+fn show_coach_review(game: &Game) {
+    println!("Review (✓ = one of the most informative guesses at the time):");
+    let optimal = optimal_guesses(game);
+    let anagram_of = anagram_guesses(&game.guesses);
+    for (i, row) in game.guesses.iter().enumerate() {
+        let mark = if optimal[i] {
+            format!("{color_green}✓{color_reset}")
+        } else {
+            format!("{color_red}✗{color_reset}")
+        };
+        match anagram_of.iter().find(|(later, _)| *later == i) {
+            Some((_, earlier)) => println!(
+                "{} {} {color_yellow}(anagram of guess {}, its letters were already known){color_reset}",
+                render_row(row, &game.theme),
+                mark,
+                earlier + 1
+            ),
+            None => println!("{} {}", render_row(row, &game.theme), mark),
+        }
+    }
+}
+
+/// Pairs of (guess, earlier guess) with the same letters in another order. The winning guess is
+/// left out, rearranging the right letters is how a game is won.
+fn anagram_guesses(guesses: &[Vec<Guess>]) -> Vec<(usize, usize)> {
+    let sorted = |row: &Vec<Guess>| {
+        let mut letters = row.iter().map(|g| g.get_letter()).collect::<Vec<char>>();
+        letters.sort();
+        letters
+    };
+    let mut pairs = Vec::new();
+    for (i, row) in guesses.iter().enumerate() {
+        if row.iter().all(|g| matches!(g, Guess::Correct(_))) {
+            continue;
+        }
+        if let Some(earlier) = (0..i).find(|j| guesses[*j] != *row && sorted(&guesses[*j]) == sorted(row)) {
+            pairs.push((i, earlier));
+        }
+    }
+    pairs
+}
+
+/// Saves the result of the game and compares a win against the player's history.
+fn finish(game: &mut Game, won: bool) {
+    let averages = game.stats.win_averages();
+    let best_time = game.stats.best_time(&game.word);
+    let record = game.record(won);
+    show_summary(game);
+    if won {
+        match averages {
+            Some((tries, seconds)) => {
+                let comparison = |value: f64, average: f64| {
+                    if value < average {
+                        "better than"
+                    } else if value > average {
+                        "worse than"
+                    } else {
+                        "the same as"
+                    }
+                };
+                println!(
+                    "{} tries, {} your average of {:.1} tries.",
+                    record.tries,
+                    comparison(record.tries as f64, tries),
+                    tries
+                );
+                println!(
+                    "{} seconds, {} your average of {:.1} seconds.",
+                    record.seconds,
+                    comparison(record.seconds as f64, seconds),
+                    seconds
+                );
+            }
+            None => println!("This is your first win, there is nothing to compare it to yet!"),
+        }
+        match best_time {
+            Some(best) if record.seconds < best => println!(
+                "{color_green}New best time on {}: {} seconds (was {} seconds){color_reset}",
+                record.word, record.seconds, best
+            ),
+            Some(best) => println!("Your best time on {} is still {} seconds.", record.word, best),
+            None => println!("First win on {}, {} seconds is the time to beat.", record.word, record.seconds),
+        }
+    }
+    save_result(game, &record);
+}
+
+/// What is shown about every finished game: the definition, the letters never tried and the coach review.
+fn show_summary(game: &Game) {
+    if let Some(definitions) = &game.definitions {
+        match definitions.get(&game.word) {
+            Some(definition) => println!("{color_cyan}{}:{color_reset} {}", game.word, definition),
+            None => println!("No definition available for {}.", game.word),
+        }
+    }
+    let unused = unused_letters(&game.guesses, &game.config.alphabet);
+    if !unused.is_empty() {
+        println!(
+            "Letters you never tried: {}",
+            unused.iter().map(|c| c.to_string()).collect::<Vec<String>>().join(" ")
+        );
+    }
+    if game.coach && !game.guesses.is_empty() {
+        show_coach_review(game);
+        let wasted = wasted_guesses(game);
+        println!("Wasted guesses: {}", wasted.len());
+        for i in wasted {
+            println!(
+                "  Guess {} ({}) didn't rule out any possible answer",
+                i + 1,
+                game.guesses[i].iter().map(|g| g.get_letter()).collect::<String>()
+            );
+        }
+    }
+}
+
+/// Writes the recorded game to the stats, the community log and the HTML export.
+fn save_result(game: &Game, record: &GameRecord) {
+    if let Err(e) = game.stats.save() {
+        println!("{color_red}Failed to save stats.{color_reset} ({})", e);
+    }
+    if let Some(path) = &game.config.community_log {
+        let entry = serde_json::json!({
+            "timestamp": SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            "word": record.word,
+            "won": record.won,
+            "tries": record.tries,
+            "seconds": record.seconds,
+        });
+        if let Err(e) = append_line(path, &entry.to_string()) {
+            println!("{color_red}Failed to write to the community log.{color_reset} ({})", e);
+        }
+    }
+    if let Some(path) = &game.export_html {
+        match write_atomic(path, render_html(game, record.won).as_bytes()) {
+            Ok(()) => println!("The board was exported to {}", path.display()),
+            Err(e) => println!("{color_red}Failed to export the board.{color_reset} ({})", e),
+        }
+    }
+}
+
+// {"cmd": "new", "length": 5, "hard": true, "max_tries": 6}
+// {"cmd": "guess", "word": "teats"}
+// {"cmd": "hint"} / {"cmd": "state"} / {"cmd": "quit"}
+#[derive(serde::Deserialize, Debug, PartialEq)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum ProtocolRequest {
+    New {
+        length: Option<usize>,
+        hard: Option<bool>,
+        max_tries: Option<u64>,
+    },
+    Guess {
+        word: String,
+    },
+    Hint,
+    State,
+    Quit,
+}
+
+#[derive(serde::Serialize, Debug)]
+struct ProtocolResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<ProtocolHint>,
+    state: ProtocolState,
+}
+
+#[derive(serde::Serialize, Debug)]
+struct ProtocolHint {
+    // from 0
+    position: usize,
+    letter: char,
+}
+
+#[derive(serde::Serialize, Debug)]
+struct ProtocolState {
+    playing: bool,
+    // "won" or "lost" once a game is over
+    outcome: Option<&'static str>,
+    length: usize,
+    max_tries: u64,
+    hints_left: u64,
+    #[serde(with = "guess_rows")]
+    rows: Vec<Vec<Guess>>,
+    // only given away when the game is over
+    answer: Option<String>,
+}
+
+/// Runs `request` against `game`. `outcome` is kept between requests, it's how the end of the
+/// last game is remembered.
+fn protocol_step(game: &mut Game, outcome: &mut Option<&'static str>, request: ProtocolRequest) -> ProtocolResponse {
+    let mut hint = None;
+    let result = match request {
+        ProtocolRequest::New { length, hard, max_tries } => match length {
+            Some(length) if game.answers_of_length(Some(length)).words.is_empty() => {
+                Err(format!("there are no {} letters answers that fit the filters in use", length))
+            }
+            _ => {
+                game.word_length = length.or(game.word_length);
+                game.hard = hard.unwrap_or(game.hard);
+                game.max_tries = max_tries.unwrap_or(game.max_tries);
+                *outcome = None;
+                game.play().map(|_| ()).map_err(|e| e.to_string())
+            }
+        },
+        ProtocolRequest::Guess { .. } | ProtocolRequest::Hint if !game.playing => {
+            Err("no game in progress, start one with new".to_string())
+        }
+        ProtocolRequest::Guess { word } => match game.determine_guess(word.trim().to_lowercase()) {
+            Ok(_) => {
+                if game.tries > game.max_tries {
+                    game.playing = false;
+                    *outcome = Some("lost");
+                }
+                Ok(())
+            }
+            Err(Errors::GameEndedWin(..)) => {
+                *outcome = Some("won");
+                Ok(())
+            }
+            Err(Errors::MaximumTries(..)) => {
+                *outcome = Some("lost");
+                Ok(())
+            }
+            Err(e) => Err(e.to_string()),
+        },
+        ProtocolRequest::Hint => game.hint().map(|(position, letter)| hint = Some(ProtocolHint { position, letter })).map_err(|e| e.to_string()),
+        ProtocolRequest::State | ProtocolRequest::Quit => Ok(()),
+    };
+    ProtocolResponse {
+        ok: result.is_ok(),
+        error: result.err(),
+        hint,
+        state: ProtocolState {
+            playing: game.playing,
+            outcome: *outcome,
+            length: game.word.chars().count(),
+            max_tries: game.max_tries,
+            hints_left: game.max_hints.saturating_sub(game.hints_used),
+            rows: game.guesses.clone(),
+            answer: outcome.map(|_| game.word.clone()),
+        },
+    }
+}
+
+/// `--protocol json-lines`: the game as a headless engine for other frontends, until `quit` or
+/// the end of stdin. Nothing is saved to the stats.
+fn run_protocol(game: &mut Game) {
+    let mut outcome = None;
+    for line in std::io::stdin().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (response, quit) = match serde_json::from_str::<ProtocolRequest>(&line) {
+            Ok(request) => {
+                let quit = request == ProtocolRequest::Quit;
+                (protocol_step(game, &mut outcome, request), quit)
+            }
+            Err(e) => {
+                let mut response = protocol_step(game, &mut outcome, ProtocolRequest::State);
+                response.ok = false;
+                response.error = Some(format!("invalid command ({})", e));
+                (response, false)
+            }
+        };
+        match serde_json::to_string(&response) {
+            Ok(json) => println!("{}", json),
+            Err(e) => println!("{{\"ok\":false,\"error\":\"{}\"}}", e),
+        }
+        if quit {
+            break;
+        }
+    }
+}
+
+/// Abandons the game in progress after the confirmation, counting it as a loss if the config says
+/// so. The board is shown first, then (with `reveal_pause`) a key press is waited for before the
+/// answer, so it isn't spoiled by accident.
+fn forfeit(game: &mut Game) {
+    if !game.guesses.is_empty() {
+        show_board(game, &game.shown_rows(&game.guesses));
+        println!();
+    }
+    if game.config.reveal_pause {
+        input(Some("Press Enter to reveal the answer..."));
+    }
+    println!("{color_red}The word was {}{color_reset}", game.word);
+    if game.config.forfeit_is_loss {
+        game.record(false);
+        if let Err(e) = game.stats.save() {
+            println!("{color_red}Failed to save stats.{color_reset} ({})", e);
+        }
+    }
+    game.playing = false;
+    game.reset();
+}
+
+fn calculate_guess_accuracy(guesses: Vec<Vec<Guess>>, hints_used: usize, scoring: &ScoringConfig) -> f64 {
+    let breakdown = AccuracyBreakdown::of(&guesses, hints_used, scoring);
+    let maximum_possible_point = guesses.first().unwrap().len() as f64 * scoring.correct; // every letter correct
+    (breakdown.points / guesses.len() as f64) / maximum_possible_point
+}
+
+/// The `--arcade` score of a win after `seconds` and `tries`, decaying exponentially from
+/// `arcade_start` so it never drops below zero.
+fn arcade_score(seconds: u64, tries: usize, scoring: &ScoringConfig) -> u64 {
+    let second_factor = (1.0 - scoring.arcade_second_decay).clamp(0.0, 1.0).powf(seconds as f64);
+    let try_factor = (1.0 - scoring.arcade_try_decay).clamp(0.0, 1.0).powf(tries.saturating_sub(1) as f64);
+    (scoring.arcade_start.max(0.0) * second_factor * try_factor).round() as u64
+}
+
+/// What the accuracy is made of, shown with `--verbose`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AccuracyBreakdown {
+    greens: usize,
+    yellows: usize,
+    grays: usize,
+    // the points of every tile of every guess minus the hint penalty
+    points: f64,
+}
+
+impl AccuracyBreakdown {
+    fn of(guesses: &[Vec<Guess>], hints_used: usize, scoring: &ScoringConfig) -> Self {
+        let mut breakdown = Self { greens: 0, yellows: 0, grays: 0, points: 0.0 };
+        for g in guesses.iter().flatten() {
+            match g {
+                Guess::Correct(_) => {
+                    breakdown.greens += 1;
+                    breakdown.points += scoring.correct;
+                }
+                Guess::Missed(_) => {
+                    breakdown.yellows += 1;
+                    breakdown.points += scoring.present;
+                }
+                Guess::Incorrect(_) => {
+                    breakdown.grays += 1;
+                    breakdown.points += scoring.absent;
+                }
+            }
+        }
+        breakdown.points -= hints_used as f64 * scoring.hint;
+        breakdown
+    }
+}
+
+fn show_accuracy_breakdown(guesses: &[Vec<Guess>], hints_used: usize, scoring: &ScoringConfig) {
+    let breakdown = AccuracyBreakdown::of(guesses, hints_used, scoring);
+    println!(
+        "  {} green, {} yellow, {} gray, {} hints: {} points over {} guesses",
+        breakdown.greens, breakdown.yellows, breakdown.grays, hints_used, breakdown.points, guesses.len()
+    );
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A standalone HTML page (inline styles, no external files) with the board of a finished game.
+fn render_html(game: &Game, won: bool) -> String {
+    let accuracy = if game.guesses.is_empty() {
+        0.0
+    } else {
+        calculate_guess_accuracy(game.scored_rows(&game.guesses), game.hints_used as usize, &game.config.scoring) * 100.0
+    };
+    let rows = game
+        .guesses
+        .iter()
+        .map(|row| {
+            let tiles = row
+                .iter()
+                .map(|guess| {
+                    let class = match guess {
+                        Guess::Correct(_) => "correct",
+                        Guess::Missed(_) => "missed",
+                        Guess::Incorrect(_) => "incorrect",
+                    };
+                    format!(
+                        "<div class=\"tile {}\">{}</div>",
+                        class,
+                        escape_html(&guess.get_letter().to_string())
+                    )
+                })
+                .collect::<String>();
+            format!("    <div class=\"row\">{}</div>\n", tiles)
+        })
+        .collect::<String>();
+    let tries = if won {
+        format!("{}/{}", game.guesses.len(), game.max_tries)
+    } else {
+        format!("X/{}", game.max_tries)
+    };
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>{title} {tries}</title>
+  <style>
+    body {{ font-family: sans-serif; background: #121213; color: #ffffff; text-align: center; }}
+    .row {{ display: flex; justify-content: center; gap: 6px; margin: 6px; }}
+    .tile {{ width: 48px; height: 48px; line-height: 48px; font-size: 28px; font-weight: bold; text-transform: uppercase; }}
+    .correct {{ background: #538d4e; }}
+    .missed {{ background: #b59f3b; }}
+    .incorrect {{ background: #3a3a3c; }}
+  </style>
+</head>
+<body>
+  <h1>{title}</h1>
+  <p>Tries: {tries}, accuracy: {accuracy:.0}%</p>
+  <div class="board">
+{rows}  </div>
+</body>
+</html>
+"#,
+        title = escape_html(&game.config.ui.banner),
+        tries = tries,
+        accuracy = accuracy,
+        rows = rows
+    )
+}
+
+fn help(ui: &UiConfig) {
+    println!("{color_cyan}{}{color_reset}", ui.banner);
+    println!(
+        "Welcome to {color_cyan}RUDLE{color_reset}! Please run the program with {bg_black}{color_bright_white}-h{color_reset}{bg_reset} for additional flags like hard mode! (or you can manually configure this inside the game)" 
+    );
+    println!(
+        "Any configurable options can be changed with {bg_black}{color_bright_white}options{color_reset}{bg_reset} as the input!"
+    );
+    println!(
+        "While playing, type {bg_black}{color_bright_white}/board{color_reset}{bg_reset} to see every guess you have made so far, {bg_black}{color_bright_white}/hint{color_reset}{bg_reset} to reveal a letter, {bg_black}{color_bright_white}/anagram{color_reset}{bg_reset} to get the letters of the word in alphabetical order (costs a hint too), {bg_black}{color_bright_white}/suggest{color_reset}{bg_reset} for a word to try or {bg_black}{color_bright_white}/quit{color_reset}{bg_reset} to give up and go back to the menu."
+    );
+    println!(
+        "Tuning a dictionary? Type {bg_black}{color_bright_white}simulate [games]{color_reset}{bg_reset} to see how many tries the solver needs on its words."
+    );
+    println!(
+        "Type {bg_black}{color_bright_white}stats{color_reset}{bg_reset} to see how you are doing ({bg_black}{color_bright_white}stats --card{color_reset}{bg_reset} to share it as text), {bg_black}{color_bright_white}leaderboard{color_reset}{bg_reset} to see your best result on every word you have won and {bg_black}{color_bright_white}affixes{color_reset}{bg_reset} to see how answers tend to start and end."
+    );
+    println!(
+        "Playing with a friend? Type {bg_black}{color_bright_white}versus{color_reset}{bg_reset} to pick a word for them to guess, or {bg_black}{color_bright_white}verify-share <answer> [win|loss]{color_reset}{bg_reset} to check that the share grid they sent you is possible."
+    );
+    println!(
+        "Curating a word list? With --reveal, type {bg_black}{color_bright_white}add-answer <file>{color_reset}{bg_reset} after a game to add its answer to a dictionary file and {bg_black}{color_bright_white}check-dictionary{color_reset}{bg_reset} to find words that can't be answers. {bg_black}{color_bright_white}diff-dict <a> <b>{color_reset}{bg_reset} shows the words only one of two word lists has."
+    );
+    println!(
+        "After a game, type {bg_black}{color_bright_white}save <file>{color_reset}{bg_reset} to keep it and {bg_black}{color_bright_white}replay <file> [delay ms]{color_reset}{bg_reset} to watch it again."
+    );
+    println!(
+        "And when you are ready to play, type {bg_black}{color_bright_white}play{color_reset}{bg_reset}!"
+    );
+}
+
+// set by --no-clear, the screen is never wiped and a separator is printed instead
+static NO_CLEAR: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+// set the first time clearscreen fails, the terminal doesn't support it so it isn't tried again
+static CLEAR_UNSUPPORTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// How many blank lines push the last turn out of sight when the screen can't be cleared.
+const CLEAR_FALLBACK_LINES: usize = 3;
+
+fn clear_screen() {
+    clear_screen_to(&mut std::io::stdout(), clearscreen::clear);
+}
+
+/// Clears the screen with `clear`, or writes a separator to `out` instead with `--no-clear` or
+/// once clearing failed.
+fn clear_screen_to(out: &mut impl Write, clear: impl FnOnce() -> Result<(), clearscreen::Error>) {
+    if NO_CLEAR.load(std::sync::atomic::Ordering::Relaxed) {
+        writeln!(out, "{}", "-".repeat(40)).ok();
+    } else if CLEAR_UNSUPPORTED.load(std::sync::atomic::Ordering::Relaxed) || clear().is_err() {
+        CLEAR_UNSUPPORTED.store(true, std::sync::atomic::Ordering::Relaxed);
+        write!(out, "{}", "\n".repeat(CLEAR_FALLBACK_LINES)).ok();
+        writeln!(out, "{}", "-".repeat(40)).ok();
+    }
+}
+
+/// Where [`input`] reads from, lines of the `--attempts-file` come first and stdin takes over once
+/// the file runs out.
+struct InputSource {
+    file: Option<std::io::BufReader<File>>,
+}
+
+impl InputSource {
+    fn next_line(&mut self) -> String {
+        if let Some(file) = &mut self.file {
+            let mut line = String::new();
+            match std::io::BufRead::read_line(file, &mut line) {
+                Ok(read) if read > 0 => {
+                    // echo it so the transcript looks like it was typed
+                    println!("{}", line.trim_end());
+                    return line;
+                }
+                _ => self.file = None,
+            }
+        }
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).unwrap() == 0 {
+            // stdin was closed, nothing more is coming (with auto_save the game in progress is
+            // already on disk)
+            println!();
+            std::process::exit(0);
+        }
+        line
+    }
+}
+
+static INPUT: std::sync::Mutex<InputSource> = std::sync::Mutex::new(InputSource { file: None });
+
+/// Like [`input`] but the typed text is not echoed, when stdin is not a terminal (piped or an
+/// attempts file) there is nothing to hide and the line is read as usual.
+fn input_hidden(ask: &str) -> String {
+    let mut source = INPUT.lock().unwrap();
+    if source.file.is_some() || !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        print!("{}", ask);
+        std::io::stdout().flush().unwrap();
+        let line = source.next_line();
+        // the echo of the attempts file only goes as far as the prompt
+        if source.file.is_none() {
+            println!();
+        }
+        return line.trim_end().to_string();
+    }
+    match rpassword::prompt_password(ask) {
+        Ok(line) => line.trim_end().to_string(),
+        Err(_) => {
+            // no controlling terminal to turn the echo off on, fall back to a visible read
+            let line = source.next_line();
+            line.trim_end().to_string()
+        }
+    }
+}
+
+fn input(ask: Option<&str>) -> String {
+    print!("{}", ask.unwrap_or(""));
+    std::io::stdout().flush().unwrap();
+    let input = INPUT.lock().unwrap().next_line();
+    input.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(words: &[&str]) -> Dictionary {
+        Dictionary {
+            words: words.iter().map(|w| w.to_string()).collect(),
+            frequencies: HashMap::new(),
+        }
+    }
+
+    /// A game of `answer` that already started, guessing from `words`.
+    fn game(words: &[&str], answer: &str) -> Game {
+        let mut game = Game::new(dictionary(words), false);
+        game.play_with(answer.to_string());
+        game
+    }
+
+    /// A fresh directory for the files of one test.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rudle-test-{}-{}", std::process::id(), name));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn row(answer: &str, guess: &str) -> Vec<Guess> {
+        feedback(answer, guess)
+    }
+
+    const WORDS: [&str; 12] = [
+        "crane", "react", "trace", "crate", "pious", "lymph", "dwelt", "sheep", "steep", "eerie", "shade", "crank",
+    ];
+
+    #[test]
+    fn random_excluding_avoids_the_previous_draw() {
+        for words in [&WORDS[..], &WORDS[..3], &WORDS[..2]] {
+            let words = dictionary(words);
+            let mut recent = VecDeque::new();
+            let mut previous = words.random_excluding(&recent).unwrap();
+            for _ in 0..50 {
+                recent.push_back(previous.clone());
+                if recent.len() > RECENT_WORDS_MEMORY {
+                    recent.pop_front();
+                }
+                let next = words.random_excluding(&recent).unwrap();
+                assert_ne!(next, previous);
+                let biased = words.random_with_bias(&HashMap::new(), SelectionBias::Practice, &recent).unwrap();
+                assert_ne!(biased, previous);
+                previous = next;
+            }
+        }
+        let single = dictionary(&["crane"]);
+        assert_eq!(single.random_excluding(&VecDeque::from(["crane".to_string()])).unwrap(), "crane");
+    }
+
+    #[test]
+    fn random_draws_fail_on_an_empty_pool() {
+        let empty = Dictionary::new();
+        assert!(matches!(empty.random_excluding(&VecDeque::new()), Err(Errors::NoWordFound)));
+        assert!(matches!(
+            empty.random_with_bias(&HashMap::new(), SelectionBias::Practice, &VecDeque::new()),
+            Err(Errors::NoWordFound)
+        ));
+    }
+
+    #[test]
+    fn alphabet_of_the_config_decides_the_letters() {
+        let mut game = game(&WORDS, "crane");
+        game.config.alphabet = "abcdefghijklmnopqrstuvwxyzñ".to_string();
+        assert!("niño".chars().all(|c| game.in_alphabet(c)));
+        assert!(!game.in_alphabet('é'));
+        game.normalize_accents = true;
+        assert!(game.in_alphabet('é'));
+        assert!(!game.in_alphabet('1'));
+    }
+
+    #[test]
+    fn word_source_parses_lists_and_frequency_maps() {
+        assert!(matches!(WordSource::parse(r#"["crane", "react"]"#), Ok(WordSource::List(words)) if words.len() == 2));
+        assert!(matches!(
+            WordSource::parse(r#"{"crane": 10, "react": 3}"#),
+            Ok(WordSource::Frequencies(words)) if words["crane"] == 10
+        ));
+        assert!(WordSource::parse(r#"{"crane": "often"}"#).is_err());
+        assert!(WordSource::parse("crane react").is_err());
+    }
+
+    #[test]
+    fn broken_bundled_word_lists_are_errors() {
+        assert!(Dictionary::try_default().is_ok());
+        for broken in [r#"["crane", "react""#, "[]", "{}", ""] {
+            assert!(Dictionary::parse(broken).is_err());
+            match Dictionary::from_bundled(broken) {
+                Err(e) => assert!(e.to_string().starts_with("the bundled words.txt is not a valid word list")),
+                Ok(_) => panic!("{:?} was accepted", broken),
+            }
+        }
+    }
+
+    #[test]
+    fn hints_stop_once_the_budget_is_spent() {
+        let mut game = game(&WORDS, "crane");
+        game.max_hints = 2;
+        assert_eq!(game.hint().unwrap(), (0, 'c'));
+        assert_eq!(game.hint().unwrap(), (1, 'r'));
+        assert!(matches!(game.hint(), Err(Errors::NoHintsLeft)));
+    }
+
+    #[test]
+    fn accents_are_normalized_but_kept_on_the_board() {
+        let mut game = game(&["café", "cafés"], "café");
+        game.normalize_accents = true;
+        match game.determine_guess("cafe".to_string()) {
+            Err(Errors::GameEndedWin(_, _, rows)) => {
+                assert_eq!(rows[0][3], Guess::Correct('é'));
+            }
+            other => panic!("expected a win, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn mercy_reveals_a_letter_after_guesses_without_progress() {
+        let mut game = game(&WORDS, "crane");
+        game.mercy = Some(2);
+        game.determine_guess("pious".to_string()).unwrap();
+        assert_eq!(game.mercy_hint, None);
+        game.determine_guess("lymph".to_string()).unwrap();
+        assert_eq!(game.mercy_hint, Some((0, 'c')));
+        // it's free, the hint budget and the accuracy don't count it
+        assert_eq!(game.hints_used, 0);
+    }
+
+    #[test]
+    fn guess_display_and_from_str_round_trip() {
+        for guess in [Guess::Correct('t'), Guess::Missed('é'), Guess::Incorrect('x')] {
+            assert_eq!(guess.to_string().parse::<Guess>(), Ok(guess));
+        }
+        let row = row("crane", "react");
+        assert_eq!(parse_row(&render_row_text(&row)), Ok(row));
+        assert!("t:green".parse::<Guess>().is_err());
+        assert!("te:correct".parse::<Guess>().is_err());
+    }
+
+    #[test]
+    fn daily_puzzles_dont_follow_the_file_order() {
+        let mut sorted = WORDS.to_vec();
+        sorted.sort();
+        let sorted = dictionary(&sorted);
+        let shuffled = dictionary(&WORDS);
+        let dailies = (0..WORDS.len() as i64).map(|i| sorted.daily(i).unwrap()).collect::<Vec<String>>();
+        assert_eq!(dailies, (0..WORDS.len() as i64).map(|i| shuffled.daily(i).unwrap()).collect::<Vec<String>>());
+        assert_ne!(dailies, sorted.words);
+        assert_eq!(dailies.iter().collect::<HashSet<&String>>().len(), WORDS.len());
+        assert_eq!(sorted.daily(WORDS.len() as i64), None);
+    }
+
+    #[test]
+    fn blocked_guesses_are_refused() {
+        let mut game = game(&WORDS, "crane");
+        game.blocked_guesses.insert("lymph".to_string());
+        assert!(matches!(game.determine_guess("lymph".to_string()), Err(Errors::GuessNotAllowed)));
+        assert!(game.determine_guess("pious".to_string()).is_ok());
+    }
+
+    #[test]
+    fn theme_file_colors_the_tiles() {
+        let path = scratch("theme").join("theme.toml");
+        std::fs::write(&path, "[correct]\nbg = \"blue\"\n\n[missed]\nfg = \"purple\"\n").unwrap();
+        let (theme, warnings) = Theme::load(&path).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(theme.correct.bg, bg_blue);
+        assert_eq!(theme.missed.fg, Theme::default().missed.fg);
+        assert!(render_tile(&Guess::Correct('a'), &theme).starts_with(bg_blue));
+    }
+
+    #[test]
+    fn coloring_modes_differ_on_extra_copies() {
+        let answer = "teats".chars().collect::<Vec<char>>();
+        let guess = "sissy".chars().collect::<Vec<char>>();
+        let standard = feedback_with(&answer, &guess, |c| c);
+        let any = feedback_any_occurrence(&answer, &guess, |c| c);
+        assert_eq!(standard.iter().filter(|g| matches!(g, Guess::Missed(_))).count(), 1);
+        assert_eq!(any.iter().filter(|g| matches!(g, Guess::Missed(_))).count(), 3);
+        assert_eq!(feedback_any_occurrence(&answer, &answer, |c| c), feedback_with(&answer, &answer, |c| c));
+    }
+
+    #[test]
+    fn candidates_follow_the_coloring_mode() {
+        let words = ["sissy", "about", "teats", "melts"];
+        for mode in [ColoringMode::Standard, ColoringMode::AnyOccurrence] {
+            let mut game = game(&words, "teats");
+            game.coloring_mode = mode;
+            game.determine_guess("sissy".to_string()).unwrap();
+            game.determine_guess("about".to_string()).unwrap();
+            assert!(game.determine_guess("teats".to_string()).is_err());
+            let answers = dictionary(&words);
+            assert!(game.candidates(&answers.words, &game.guesses[..2]).contains(&&"teats".to_string()));
+            assert_eq!(wasted_guesses(&game), Vec::<usize>::new());
+        }
+    }
+
+    #[test]
+    fn wasted_guesses_of_an_answer_outside_the_dictionary() {
+        let mut game = game(&["pious", "crane", "react", "trace", "lymph"], "crate");
+        for guess in ["pious", "crane", "lymph"] {
+            game.determine_guess(guess.to_string()).unwrap();
+        }
+        // only "crate" was left after "crane", so "lymph" ruled nothing out
+        assert_eq!(wasted_guesses(&game), vec![2]);
+    }
+
+    #[test]
+    fn pattern_entropy_follows_the_coloring_mode() {
+        // only the standard coloring tells one extra "e" from two
+        let (one, two) = ("aebcd".to_string(), "aebed".to_string());
+        let candidates = [&one, &two];
+        let mut game = game(&WORDS, "crane");
+        let score = |answer: &str, guess: &str| game.score(answer, guess);
+        assert_eq!(pattern_entropy("eerie", &candidates, &score), 1.0);
+        game.coloring_mode = ColoringMode::AnyOccurrence;
+        let score = |answer: &str, guess: &str| game.score(answer, guess);
+        assert_eq!(pattern_entropy("eerie", &candidates, &score), 0.0);
+    }
+
+    #[test]
+    fn coach_review_of_a_recorded_game() {
+        let words = ["sissy", "about", "teats", "melts", "crane", "lymph", "pious"];
+        let mut game = game(&words, "teats");
+        game.coloring_mode = ColoringMode::AnyOccurrence;
+        for guess in ["lymph", "sissy", "teats"] {
+            game.determine_guess(guess.to_string()).ok();
+        }
+        // lymph only tells itself and melts apart, after sissy teats is the only answer left
+        assert_eq!(optimal_guesses(&game), vec![false, true, true]);
+    }
+
+    #[test]
+    fn fog_hides_yellows_but_still_wins() {
+        let mut game = game(&["teats", "stare", "quack", "treat"], "teats");
+        game.fog = true;
+        game.hard = true;
+        let row = game.determine_guess("stare".to_string()).unwrap();
+        assert!(row.iter().any(|g| matches!(g, Guess::Missed(_))));
+        let shown = game.shown_rows(&game.guesses);
+        assert!(shown.iter().flatten().all(|g| !matches!(g, Guess::Missed(_))));
+        assert!(render_board(&game, false).contains(&render_row(&shown[0], &game.theme)));
+        // hard mode only asks for the greens that were shown
+        game.determine_guess("quack".to_string()).unwrap();
+        assert!(matches!(game.determine_guess("teats".to_string()), Err(Errors::GameEndedWin(4, _, _))));
+        assert_eq!(game.guesses.last().unwrap(), &feedback("teats", "teats"));
+    }
+
+    #[test]
+    fn path_overrides_win_over_the_platform_directories() {
+        let platform = Some(PathBuf::from("/home/someone/.local/share/rudle"));
+        let own = Some(std::ffi::OsString::from("/tmp/data"));
+        let config = Some(std::ffi::OsString::from("/tmp/config"));
+        assert_eq!(paths::resolve(own.clone(), config.clone(), platform.clone()), Some(PathBuf::from("/tmp/data")));
+        assert_eq!(paths::resolve(None, config, platform.clone()), Some(PathBuf::from("/tmp/config")));
+        assert_eq!(paths::resolve(None, None, platform.clone()), platform);
+    }
+
+    #[test]
+    fn auto_save_resumes_the_game() {
+        let mut played = game(&WORDS, "crane");
+        played.dictionaries.insert("other".to_string(), dictionary(&["lymph"]));
+        played.mercy = Some(1);
+        played.determine_guess("pious".to_string()).unwrap();
+        played.hint().unwrap();
+        assert_eq!((played.hints.len(), played.hints_used), (2, 1));
+        let path = scratch("autosave").join("autosave.json");
+        save_json(&path, &AutoSave::of(&played)).unwrap();
+
+        let mut resumed = Game::new(dictionary(&["lymph"]), false);
+        resumed.active_dictionary = "other".to_string();
+        resumed.dictionaries.insert("default".to_string(), dictionary(&WORDS));
+        let note = AutoSave::load_from(&path).unwrap().restore(&mut resumed);
+        assert!(note.is_some());
+        assert_eq!(resumed.active_dictionary, "default");
+        assert_eq!(resumed.word, "crane");
+        assert_eq!(resumed.guesses, played.guesses);
+        assert_eq!(resumed.tries, played.tries);
+        assert_eq!(resumed.hints, played.hints);
+        assert_eq!(resumed.hints_used, 1);
+        assert!(matches!(resumed.determine_guess("crane".to_string()), Err(Errors::GameEndedWin(..))));
+    }
+
+    #[test]
+    fn auto_save_adds_an_answer_missing_from_the_dictionary() {
+        let played = game(&["crane"], "crane");
+        let mut resumed = Game::new(dictionary(&["lymph"]), false);
+        assert!(AutoSave::of(&played).restore(&mut resumed).is_some());
+        assert!(matches!(resumed.determine_guess("crane".to_string()), Err(Errors::GameEndedWin(..))));
+    }
+
+    #[test]
+    fn cached_pattern_table_matches_a_fresh_one() {
+        let mut game = game(&WORDS, "crane");
+        game.coloring_mode = ColoringMode::AnyOccurrence;
+        let words = WORDS.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        let words = words.iter().collect::<Vec<&String>>();
+        let cache = scratch("patterns");
+        let fresh = PatternTable::load_or_build(&game, &words, None);
+        let built = PatternTable::load_or_build(&game, &words, Some(cache.clone()));
+        let cached = PatternTable::load_or_build(&game, &words, Some(cache.clone()));
+        assert_eq!(built.patterns, fresh.patterns);
+        assert_eq!(cached.patterns, fresh.patterns);
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 1);
+
+        game.determine_guess("eerie".to_string()).unwrap();
+        let answer = words.iter().position(|w| *w == "crane").unwrap();
+        assert!(cached.candidates(&game.guesses).contains(&answer));
+    }
+
+    #[test]
+    fn team_players_take_turns() {
+        let mut game = game(&WORDS, "crane");
+        game.team = true;
+        let mut players = vec![game.player()];
+        for guess in ["pious", "lymph", "dwelt", "sheep"] {
+            game.determine_guess(guess.to_string()).unwrap();
+            players.push(game.player());
+            assert_eq!(game.mid_round(), game.guesses.len() % 2 == 1);
+        }
+        assert_eq!(players, vec![1, 2, 1, 2, 1]);
+    }
+
+    #[test]
+    fn pattern_table_ignores_a_cache_with_a_bad_header() {
+        let game = game(&WORDS, "crane");
+        let words = WORDS.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        let words = words.iter().collect::<Vec<&String>>();
+        let cache = scratch("patterns-header");
+        let fresh = PatternTable::load_or_build(&game, &words, Some(cache.clone()));
+        let path = std::fs::read_dir(&cache).unwrap().next().unwrap().unwrap().path();
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] = b'X';
+        bytes[PATTERN_TABLE_HEADER] ^= 0xff;
+        std::fs::write(&path, bytes).unwrap();
+        assert_eq!(PatternTable::load_or_build(&game, &words, Some(cache)).patterns, fresh.patterns);
+    }
+
+    #[test]
+    fn same_seed_scrambles_the_keyboard_the_same_way() {
+        let layout = |seed: Option<u64>| {
+            let mut game = Game::new(dictionary(&WORDS), false);
+            game.tournament = seed;
+            game.scramble_keyboard = true;
+            let word = game.tournament_word().unwrap();
+            game.play_with(word);
+            keyboard_layout(game.scramble_keyboard.then_some(game.keyboard_seed))
+        };
+        let seed = phrase_seed("friday night");
+        assert_eq!(layout(Some(seed)), layout(Some(seed)));
+        assert_eq!(keyboard_layout(Some(seed)), keyboard_layout(Some(seed)));
+        assert_ne!(keyboard_layout(Some(seed)), keyboard_layout(None));
+    }
+
+    #[test]
+    fn closest_guess_has_the_most_greens() {
+        let guesses = [row("crane", "pious"), row("crane", "crate"), row("crane", "react")];
+        assert_eq!(closest_guess(&guesses), Some((1, 4)));
+    }
+
+    #[test]
+    fn hardcore_only_ends_on_rejected_guesses() {
+        assert!(Errors::NoWordFound.is_rejected_guess());
+        assert!(Errors::InvalidWordInHardMode.is_rejected_guess());
+        assert!(!Errors::NoHintsLeft.is_rejected_guess());
+        assert!(!Errors::GameEndedWin(2, 5, Vec::new()).is_rejected_guess());
+
+        let mut normal = game(&WORDS, "crane");
+        let (error, _) = normal.submit_guess("crame".to_string()).unwrap_err();
+        assert!(normal.hardcore_loss(&error).is_none());
+        assert!(normal.playing);
+        assert!(normal.stats.games.is_empty());
+
+        let mut hardcore = game(&WORDS, "crane");
+        hardcore.hardcore = true;
+        assert!(hardcore.hardcore_loss(&Errors::NoHintsLeft).is_none());
+        let (error, _) = hardcore.submit_guess("crame".to_string()).unwrap_err();
+        let record = hardcore.hardcore_loss(&error).unwrap();
+        assert!(!hardcore.playing);
+        assert!(!record.won);
+        assert_eq!(hardcore.stats.games.len(), 1);
+        assert!(!hardcore.stats.games[0].won);
+    }
+
+    #[test]
+    fn suggestion_ties_are_broken_alphabetically() {
+        let candidates = ["dog".to_string(), "cat".to_string()];
+        let candidates = candidates.iter().collect::<Vec<&String>>();
+        assert_eq!(suggest(&candidates), Some("cat".to_string()));
+        let reversed = candidates.iter().rev().copied().collect::<Vec<&String>>();
+        assert_eq!(suggest(&reversed), Some("cat".to_string()));
+    }
+
+    #[test]
+    fn letter_bank_narrows_with_greens_and_yellows() {
+        let alphabet = "abcdefghijklmnopqrstuvwxyz".chars().collect::<Vec<char>>();
+        let bank = letter_bank(5, &[row("crane", "react")], &alphabet);
+        assert_eq!(bank[2], vec!['a']);
+        assert!(!bank[0].contains(&'r') && bank[0].contains(&'e'));
+        assert!(!bank[3].contains(&'c'));
+        assert!(bank.iter().all(|letters| !letters.contains(&'t')));
+    }
+
+    #[test]
+    fn max_repeats_drops_triple_letters() {
+        let words = dictionary(&["eerie", "sheep", "crane"]).filter_max_repeats(2).words;
+        assert_eq!(words, vec!["sheep", "crane"]);
+    }
+
+    #[test]
+    fn corrupt_json_falls_back_to_the_default() {
+        let path = scratch("corrupt").join("stats.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        let (stats, warning) = load_json::<Stats>(&path);
+        assert!(stats.games.is_empty());
+        assert!(warning.is_some());
+        assert!(path.with_file_name("stats.json.bak").exists());
+    }
+
+    #[test]
+    fn interrupted_write_leaves_the_old_file() {
+        let path = scratch("atomic").join("stats.json");
+        write_atomic(&path, b"old").unwrap();
+        // what a crash between writing and renaming leaves behind
+        std::fs::write(path.with_file_name("stats.json.tmp"), b"ne").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"old");
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        assert!(!path.with_file_name("stats.json.tmp").exists());
+    }
+
+    #[test]
+    fn position_frequencies_count_letters_per_position() {
+        let frequencies = dictionary(&["abc", "abd", "xbc", "abcd"]).position_frequencies(3);
+        assert_eq!(frequencies[0], vec![('a', 2), ('x', 1)]);
+        assert_eq!(frequencies[1], vec![('b', 3)]);
+        assert_eq!(frequencies[2], vec![('c', 2), ('d', 1)]);
+    }
+
+    #[test]
+    fn broken_answers_are_reported() {
+        let blocked = HashSet::from(["lymph".to_string()]);
+        let invalid = dictionary(&["crane", "cran3", "toolong", "lymph"]).validate_answers(5, &blocked);
+        assert_eq!(invalid, vec!["cran3", "toolong", "lymph"]);
+    }
+
+    #[test]
+    fn numbered_rows_are_prefixed_with_the_try() {
+        let rows = render_numbered_rows(&[row("crane", "react"), row("crane", "crane")], &Theme::default(), 2);
+        let lines = rows.lines().filter(|l| !l.is_empty()).collect::<Vec<&str>>();
+        assert!(lines[0].starts_with(" 1: "));
+        assert!(lines[1].starts_with(" 2: "));
+    }
+
+    #[test]
+    fn target_word_is_checked_and_played() {
+        let mut game = Game::new(dictionary(&WORDS), false);
+        game.fixed_word = Some(game.target_word(" Crane\n").unwrap());
+        assert_eq!(game.play().unwrap(), "crane");
+        assert!(matches!(game.target_word("zebra"), Err(Errors::NoWordFound)));
+        game.word_length = Some(4);
+        assert!(matches!(game.target_word("crane"), Err(Errors::WordLengthNotEqualsToGuessWord)));
+    }
+
+    #[test]
+    fn top_common_keeps_the_most_frequent_words() {
+        let words = Dictionary::parse(r#"{"aaa": 5, "bbb": 9, "ccc": 1}"#).unwrap();
+        assert_eq!(words.top_common(2).unwrap().words, vec!["aaa", "bbb"]);
+        assert!(dictionary(&["aaa"]).top_common(1).is_err());
+    }
+
+    #[test]
+    fn hourly_puzzles_change_every_hour() {
+        let hour = UNIX_EPOCH + std::time::Duration::from_secs(3600 * 500_000);
+        let later = hour + std::time::Duration::from_secs(3599);
+        let next = hour + std::time::Duration::from_secs(3600);
+        assert_eq!(hourly_index(hour), hourly_index(later));
+        assert_eq!(hourly_index(next), hourly_index(hour) + 1);
+        let game = game(&WORDS, "crane");
+        let (_, this) = game.hourly_puzzle(hourly_index(hour)).unwrap();
+        let (_, that) = game.hourly_puzzle(hourly_index(next)).unwrap();
+        assert_ne!(this, that);
+        let hours = (0..WORDS.len() as i64).map(|i| game.hourly_puzzle(i).unwrap().1).collect::<Vec<String>>();
+        assert_eq!(hours, game.answers().puzzle_order());
+        assert_eq!(game.hourly_puzzle(WORDS.len() as i64).unwrap().1, hours[0]);
+    }
+
+    #[test]
+    fn non_letters_get_their_own_error() {
+        let mut game = game(&WORDS, "crane");
+        match game.determine_guess("12345".to_string()) {
+            Err(Errors::InvalidCharacters(characters)) => assert_eq!(characters, vec!['1', '2', '3', '4', '5']),
+            other => panic!("expected invalid characters, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn progressive_hints_start_at_half_the_tries() {
+        let mut game = game(&WORDS, "crane");
+        game.progressive_hints = true;
+        game.max_tries = 6;
+        game.determine_guess("pious".to_string()).unwrap();
+        game.determine_guess("lymph".to_string()).unwrap();
+        assert_eq!(game.mercy_hint, None);
+        game.determine_guess("dwelt".to_string()).unwrap();
+        assert_eq!(game.mercy_hint, Some((0, 'c')));
+        game.determine_guess("sheep".to_string()).unwrap();
+        assert_eq!(game.mercy_hint, Some((1, 'r')));
+    }
+
+    #[test]
+    fn practice_bias_favors_lost_words() {
+        let words = dictionary(&["crane", "lymph", "pious"]);
+        let losses = HashMap::from([("lymph".to_string(), 3)]);
+        let mut counts = HashMap::<String, usize>::new();
+        for _ in 0..3000 {
+            let word = words.random_with_bias(&losses, SelectionBias::Practice, &VecDeque::new()).unwrap();
+            *counts.entry(word).or_default() += 1;
+        }
+        assert!(counts["lymph"] > counts["crane"] * 2);
+        assert!(counts["lymph"] > counts["pious"] * 2);
+    }
+
+    #[test]
+    fn length_policies_fit_the_guess() {
+        assert_eq!(sanitize_guess("cranes", 5, LengthPolicy::Reject), None);
+        assert_eq!(sanitize_guess("cra", 5, LengthPolicy::Reject), None);
+        assert_eq!(sanitize_guess("cranes", 5, LengthPolicy::Truncate), Some(("crane".to_string(), true)));
+        assert_eq!(sanitize_guess("cra", 5, LengthPolicy::Truncate), None);
+        assert_eq!(sanitize_guess("cranes", 5, LengthPolicy::Pad), Some(("crane".to_string(), true)));
+        assert_eq!(sanitize_guess("cra", 5, LengthPolicy::Pad), Some(("craaa".to_string(), true)));
+        assert_eq!(sanitize_guess("crane", 5, LengthPolicy::Pad), Some(("crane".to_string(), false)));
+    }
+
+    #[test]
+    fn personal_best_only_improves() {
+        let mut stats = Stats::default();
+        let record = |tries, seconds| GameRecord { word: "crane".to_string(), won: true, tries, seconds };
+        assert!(stats.update_best(&record(4, 60)));
+        assert!(!stats.update_best(&record(5, 10)));
+        assert!(!stats.update_best(&record(4, 60)));
+        assert!(stats.update_best(&record(4, 30)));
+        assert_eq!(stats.best["crane"], PersonalBest { tries: 4, seconds: 30 });
+    }
+
+    #[test]
+    fn community_log_gets_a_line_per_game() {
+        let path = scratch("community").join("log.jsonl");
+        for won in [true, false] {
+            append_line(&path, &serde_json::json!({ "word": "crane", "won": won }).to_string()).unwrap();
+        }
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines = contents.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            assert!(serde_json::from_str::<serde_json::Value>(line).is_ok());
+        }
+    }
+
+    #[test]
+    fn possible_answers_match_a_brute_force_filter() {
+        let mut game = game(&WORDS, "crane");
+        game.determine_guess("react".to_string()).unwrap();
+        game.determine_guess("crate".to_string()).unwrap();
+        let expected = WORDS
+            .iter()
+            .filter(|w| ["react", "crate"].iter().all(|guess| feedback(w, guess) == feedback("crane", guess)))
+            .copied()
+            .collect::<Vec<&str>>();
+        assert_eq!(game.possible_answers_iter().collect::<Vec<&str>>(), expected);
+        assert!(expected.contains(&"crane"));
+    }
+
+    #[test]
+    fn filters_shrink_the_answer_pool() {
+        let mut game = game(&WORDS, "crane");
+        let all = game.answers().words.len();
+        game.required_letters = vec!['c'];
+        let with_c = game.answers().words.len();
+        game.fixed_letter = Some((0, 'c'));
+        let starting_with_c = game.answers().words.len();
+        assert!(all > with_c && with_c > starting_with_c && starting_with_c > 0);
+    }
+
+    #[test]
+    fn scoring_weights_change_the_accuracy() {
+        let rows = vec![row("crane", "react"), row("crane", "crane")];
+        let standard = ScoringConfig::default();
+        let generous = ScoringConfig { present: 2.0, ..ScoringConfig::default() };
+        let accuracy = calculate_guess_accuracy(rows.clone(), 0, &standard);
+        assert_eq!(accuracy, (5.0 - 0.5 + 10.0) / 2.0 / 10.0);
+        assert!(calculate_guess_accuracy(rows.clone(), 0, &generous) > accuracy);
+        assert!(calculate_guess_accuracy(rows, 1, &standard) < accuracy);
+    }
+
+    #[test]
+    fn anagram_hint_gives_the_sorted_letters() {
+        let mut game = game(&WORDS, "crane");
+        assert_eq!(game.anagram_hint().unwrap(), "acenr");
+        assert_eq!(game.hints_used, 1);
+        // asking again is free
+        assert_eq!(game.anagram_hint().unwrap(), "acenr");
+        assert_eq!(game.hints_used, 1);
+    }
+
+    #[test]
+    fn anagram_hint_costs_accuracy_and_works_outside_the_dictionary() {
+        let mut outside = game(&["react", "pious"], "crane");
+        outside.determine_guess("react".to_string()).unwrap();
+        assert_eq!(outside.possible_answers_iter().count(), 0);
+        assert_eq!(outside.anagram_hint().unwrap(), "acenr");
+        let rows = outside.scored_rows(&outside.guesses);
+        let scoring = ScoringConfig::default();
+        assert!(calculate_guess_accuracy(rows.clone(), outside.hints_used as usize, &scoring) < calculate_guess_accuracy(rows, 0, &scoring));
+
+        let mut solved = game(&WORDS, "lymph");
+        solved.determine_guess("pious".to_string()).unwrap();
+        solved.determine_guess("dwelt".to_string()).unwrap();
+        solved.determine_guess("shade".to_string()).unwrap();
+        assert!(matches!(solved.anagram_hint(), Err(Errors::NothingToHint)));
+    }
+
+    #[test]
+    fn min_difficulty_shrinks_the_pool() {
+        let words = dictionary(&WORDS);
+        let easy = words.with_min_difficulty(Difficulty::Easy).words.len();
+        let medium = words.with_min_difficulty(Difficulty::Medium).words.len();
+        let hard = words.with_min_difficulty(Difficulty::Hard).words.len();
+        assert_eq!(easy, WORDS.len());
+        assert!(easy > medium && medium > hard && hard > 0);
+    }
+
+    #[test]
+    fn fixed_letter_candidates_all_have_it() {
+        let words = dictionary(&["happy", "crane", "lymph", "tipsy"]).with_fixed(4, 'y').words;
+        assert_eq!(words, vec!["happy", "tipsy"]);
+    }
+
+    #[test]
+    fn rejected_guesses_are_handed_back() {
+        let mut game = game(&WORDS, "crane");
+        match game.submit_guess("crame".to_string()) {
+            Err((Errors::NoWordFound, typed)) => assert_eq!(typed.as_deref(), Some("crame")),
+            other => panic!("unexpected {:?}", other),
+        }
+        match game.submit_guess("cran".to_string()) {
+            Err((Errors::WordLengthNotEqualsToGuessWord, typed)) => assert_eq!(typed.as_deref(), Some("cran")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(game.submit_guess("pious".to_string()).is_ok());
+        assert!(matches!(game.submit_guess("crane".to_string()), Err((Errors::GameEndedWin(..), None))));
+    }
+
+    #[test]
+    fn failed_clear_falls_back_to_a_separator() {
+        let mut out = Vec::new();
+        clear_screen_to(&mut out, || Err(clearscreen::Error::Io(std::io::ErrorKind::Unsupported.into())));
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}{}\n", "\n".repeat(CLEAR_FALLBACK_LINES), "-".repeat(40)));
+    }
+
+    #[test]
+    fn alphabet_strip_dims_used_letters() {
+        let strip = alphabet_strip(&[row("crane", "lymph")], "abclm");
+        assert!(strip.contains(&format!("{}L{color_reset}", color_bright_black)));
+        assert!(strip.contains(&format!("{}A{color_reset}", color_bright_white)));
+    }
+
+    #[test]
+    fn loss_reveal_has_no_delay_by_default() {
+        assert_eq!(Config::default().loss_reveal_delay_ms, 0);
+    }
+
+    #[test]
+    fn lengths_are_counted_for_mixed_lists() {
+        let words = dictionary(&["abcde", "abc", "abd", "ab"]);
+        assert_eq!(words.lengths(), vec![(2, 1), (3, 2), (5, 1)]);
+        assert!(words.contains_length(3));
+        assert!(!words.contains_length(4));
+    }
+
+    #[test]
+    fn next_daily_is_offered_until_today() {
+        let stats = Stats { dailies: vec![5, 6], ..Stats::default() };
+        assert_eq!(stats.next_unplayed_daily(4, 8), Some(7));
+        assert_eq!(stats.next_unplayed_daily(7, 8), Some(8));
+        assert_eq!(stats.next_unplayed_daily(8, 8), None);
+    }
+
+    #[test]
+    fn word_lists_are_compared() {
+        let list = |words: &[&str]| words.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        let (only_a, only_b, both) =
+            compare_word_lists(&list(&["crane", "pious", "lymph", "crane"]), &list(&["lymph", "dwelt", "crane"]));
+        assert_eq!(only_a, list(&["pious"]));
+        assert_eq!(only_b, list(&["dwelt"]));
+        assert_eq!(both, list(&["crane", "lymph"]));
+    }
+
+    #[test]
+    fn reset_forgets_the_daily_puzzle() {
+        let mut game = Game::new(dictionary(&WORDS), false);
+        let now = SystemTime::now();
+        game.daily = Some(3 - daily_index(now, 0));
+        game.play_at(now).unwrap();
+        assert_eq!(game.daily_number, Some(3));
+        game.reset();
+        assert_eq!(game.daily_number, None);
+    }
+
+    #[test]
+    fn accuracy_breakdown_counts_the_tiles() {
+        let breakdown = AccuracyBreakdown::of(&[row("crane", "react"), row("crane", "crane")], 1, &ScoringConfig::default());
+        assert_eq!(breakdown.greens, 6);
+        assert_eq!(breakdown.yellows, 3);
+        assert_eq!(breakdown.grays, 1);
+        assert_eq!(breakdown.points, 12.0 + 3.0 - 0.5 - 2.0);
+    }
+
+    #[test]
+    fn tournament_order_follows_the_seed() {
+        let words = dictionary(&WORDS);
+        assert_eq!(words.shuffled(42), words.shuffled(42));
+        assert_ne!(words.shuffled(42), words.shuffled(43));
+    }
+
+    #[test]
+    fn anagram_guesses_are_flagged() {
+        let guesses = [row("crane", "react"), row("crane", "trace"), row("crane", "pious"), row("crane", "crane")];
+        assert_eq!(anagram_guesses(&guesses), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn protocol_round_trip() {
+        let mut game = Game::new(dictionary(&["crane"]), false);
+        let mut outcome = None;
+        let new = serde_json::from_str::<ProtocolRequest>(r#"{"cmd": "new", "max_tries": 3}"#).unwrap();
+        let response = protocol_step(&mut game, &mut outcome, new);
+        assert!(response.ok);
+        assert_eq!(response.state.length, 5);
+        assert_eq!(response.state.answer, None);
+        let guess = serde_json::from_str::<ProtocolRequest>(r#"{"cmd": "guess", "word": "crane"}"#).unwrap();
+        let response = protocol_step(&mut game, &mut outcome, guess);
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["ok"], true);
+        assert_eq!(json["state"]["outcome"], "won");
+        assert_eq!(json["state"]["answer"], "crane");
+        assert_eq!(json["state"]["rows"][0], render_row_text(&row("crane", "crane")));
+        let hint = serde_json::from_str::<ProtocolRequest>(r#"{"cmd": "hint"}"#).unwrap();
+        assert!(!protocol_step(&mut game, &mut outcome, hint).ok);
+    }
+
+    #[test]
+    fn balanced_daily_is_the_same_for_a_day() {
+        let words = dictionary(&WORDS);
+        let ratings = words.difficulty();
+        let word = words.daily_balanced(12).unwrap();
+        assert_eq!(words.daily_balanced(12), Some(word.clone()));
+        assert_eq!(ratings[&word], Difficulty::Medium);
+    }
+
+    #[test]
+    fn share_grids_are_checked_against_the_answer() {
+        let words = WORDS.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        let grid = parse_share_grid("Rudle 2/6\n🟨🟨🟩🟨⬛\n🟩🟩🟩🟩🟩").unwrap();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(share_grid_problem(&grid, "crane", true, &words), None);
+        assert!(share_grid_problem(&grid, "crane", false, &words).is_some());
+        let tampered = parse_share_grid("🟩🟩🟩🟩🟨\n🟩🟩🟩🟩🟩").unwrap();
+        assert_eq!(
+            share_grid_problem(&tampered, "crane", true, &words),
+            Some("no word gives the colors of row 1".to_string())
+        );
+    }
+
+    #[test]
+    fn time_budget_runs_out() {
+        let mut game = game(&WORDS, "crane");
+        let seconds = std::time::Duration::from_secs;
+        assert_eq!(game.time_left(seconds(30)), None);
+        game.time_budget = Some(60);
+        assert_eq!(game.time_left(seconds(20)), Some(seconds(40)));
+        assert_eq!(game.time_left(seconds(60)), Some(seconds(0)));
+        assert_eq!(game.time_left(seconds(90)), Some(seconds(0)));
+    }
+
+    #[test]
+    fn common_affixes_of_a_small_list() {
+        let (prefixes, suffixes) = dictionary(&["stair", "stale", "crane", "plane"]).common_affixes(2);
+        assert_eq!(prefixes, vec![("st".to_string(), 2), ("cr".to_string(), 1)]);
+        assert_eq!(suffixes, vec![("ne".to_string(), 2), ("ir".to_string(), 1)]);
+    }
+
+    #[test]
+    fn changes_underline_only_new_letters() {
+        let rendered = render_row_changes(&row("crane", "crane"), &row("crane", "crate"), &Theme::default());
+        assert_eq!(rendered.matches(style_underline).count(), 1);
+        let underlined = rendered.split(' ').position(|tile| tile.contains(style_underline));
+        assert_eq!(underlined, Some(3));
+    }
+
+    #[test]
+    fn stats_card_has_the_key_figures() {
+        let record = |won, tries| GameRecord { word: "crane".to_string(), won, tries, seconds: 10 };
+        let stats = Stats { games: vec![record(true, 3), record(false, 6)], ..Stats::default() };
+        let card = stats_card(&stats);
+        assert!(card.contains("Played 2, won 50%"));
+        assert!(card.contains("Streak 0, best streak 1"));
+        assert!(card.lines().all(|line| line.chars().count() == STATS_CARD_WIDTH + 4));
+    }
+
+    #[test]
+    fn risky_guesses_cost_two_tries() {
+        let mut game = game(&WORDS, "crane");
+        game.risky = true;
+        game.determine_guess("zzzzz".to_string()).unwrap();
+        assert_eq!(game.tries, 3);
+        game.determine_guess("react".to_string()).unwrap();
+        assert_eq!(game.tries, 4);
+    }
+
+    #[test]
+    fn definitions_are_looked_up_ignoring_case() {
+        let definitions = Definitions(HashMap::from([("crane".to_string(), "a tall bird".to_string())]));
+        assert_eq!(definitions.get("Crane"), Some("a tall bird"));
+        assert_eq!(definitions.get("react"), None);
+    }
+
+    #[test]
+    fn centered_board_is_padded_evenly() {
+        let aligned = align_block("abc\n\nabcde", "center", 11);
+        assert_eq!(aligned, "   abc\n\n   abcde");
+        assert_eq!(align_block("abc", "right", 5), "  abc");
+        assert_eq!(align_block("abc", "left", 5), "abc");
+    }
+
+    #[test]
+    fn second_mulligan_is_refused() {
+        let mut game = game(&WORDS, "crane");
+        game.determine_guess("pious".to_string()).unwrap();
+        game.determine_guess("lymph".to_string()).unwrap();
+        game.mulligan().unwrap();
+        assert_eq!(game.guesses.len(), 1);
+        assert!(matches!(game.mulligan(), Err(Errors::MulliganUsed)));
+        assert_eq!(game.scored_rows(&game.guesses).len(), 2);
+    }
+
+    #[test]
+    fn mulligan_refunds_what_the_guess_cost() {
+        let mut game = game(&WORDS, "crane");
+        game.risky = true;
+        game.determine_guess("zzzzz".to_string()).unwrap();
+        game.mulligan().unwrap();
+        assert_eq!(game.tries, 1);
+    }
+
+    #[test]
+    fn mulligan_keeps_the_team_turns() {
+        let mut game = game(&WORDS, "crane");
+        game.team = true;
+        game.determine_guess("pious".to_string()).unwrap();
+        game.determine_guess("lymph".to_string()).unwrap();
+        game.mulligan().unwrap();
+        // player 2 took the guess back, player 1 still goes next
+        assert_eq!(game.player(), 1);
+        assert!(!game.mid_round());
+    }
+
+    #[test]
+    fn length_prompt_offers_the_lengths_with_answers() {
+        let mut game = Game::new(dictionary(&["abcde", "qzxyw", "abc", "abd"]), false);
+        assert_eq!(game.answer_lengths(), vec![(3, 2), (5, 2)]);
+        game.required_letters = vec!['q'];
+        assert_eq!(game.answer_lengths(), vec![(5, 1)]);
+    }
+
+    #[test]
+    fn nearest_guess_has_the_fewest_edits() {
+        let guesses = [row("crane", "pious"), row("crane", "crate"), row("crane", "crank"), row("crane", "react")];
+        assert_eq!(nearest_guess("crane", &guesses), Some(("crate".to_string(), 1)));
+        assert_eq!(levenshtein("react", "crane"), 4);
+    }
+
+    #[test]
+    fn faster_solves_score_higher() {
+        let scoring = ScoringConfig::default();
+        assert!(arcade_score(20, 3, &scoring) > arcade_score(60, 3, &scoring));
+        assert!(arcade_score(20, 2, &scoring) > arcade_score(20, 3, &scoring));
+        assert_eq!(arcade_score(0, 1, &scoring), 10000);
+    }
+
+    #[test]
+    fn tray_holds_both_copies_of_a_double_letter() {
+        let mut guesses = vec![row("sheep", "eerie")];
+        assert_eq!(letter_tray(&guesses), vec!['e', 'e']);
+        guesses.push(row("sheep", "shade"));
+        assert_eq!(letter_tray(&guesses), vec!['e', 'e']);
+        guesses.push(row("sheep", "steep"));
+        assert_eq!(letter_tray(&guesses), Vec::<char>::new());
+    }
+
+    #[test]
+    fn same_phrase_same_seed_and_words() {
+        assert_eq!(phrase_seed("friday night"), phrase_seed("friday night"));
+        assert_ne!(phrase_seed("friday night"), phrase_seed("friday nite"));
+        let draw = |phrase: &str| {
+            let mut game = Game::new(dictionary(&WORDS), false);
+            game.tournament = Some(phrase_seed(phrase));
+            (0..3).map(|_| game.tournament_word().unwrap()).collect::<Vec<String>>()
+        };
+        assert_eq!(draw("friday night"), draw("friday night"));
+    }
 }